@@ -1,7 +1,41 @@
 use std::f32::consts::PI;
 
+/// Meters.
 pub const EARTH_MEAN_RADIUS: f32 = 6371007.2;
+/// Kilometers.
 pub const EARTH_MEAN_RADIUS_KM: f32 = 6371.0072;
+/// Meters.
 pub const EARTH_MEAN_CIRCUMFERENCE: f32 = EARTH_MEAN_RADIUS * 2.0 * PI;
+/// Kilometers.
 pub const EARTH_MEAN_CIRCUMFERENCE_KM: f32 = EARTH_MEAN_RADIUS_KM * 2.0 * PI;
+/// Degrees.
 pub const MERCATOR_MAX_LATITUDE: f64 = 85.05113;
+
+/// WGS84 equatorial radius `a`, meters.
+pub const WGS84_SEMI_MAJOR_AXIS: f64 = 6378137.0;
+/// WGS84 flattening `f`, dimensionless.
+pub const WGS84_FLATTENING: f64 = 1.0 / 298.257223563;
+/// WGS84 polar radius `b = a * (1 - f)`, meters.
+pub const WGS84_SEMI_MINOR_AXIS: f64 = WGS84_SEMI_MAJOR_AXIS * (1.0 - WGS84_FLATTENING);
+/// WGS84 first eccentricity squared `e² = f * (2 - f)`, dimensionless.
+pub const WGS84_ECCENTRICITY_SQUARED: f64 = WGS84_FLATTENING * (2.0 - WGS84_FLATTENING);
+/// GRS80 equatorial radius `a`, meters.
+pub const GRS80_SEMI_MAJOR_AXIS: f64 = 6378137.0;
+/// GRS80 flattening `f`, dimensionless.
+pub const GRS80_FLATTENING: f64 = 1.0 / 298.257222101;
+/// International nautical mile, meters.
+pub const NAUTICAL_MILE: f32 = 1852.0;
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_wgs84_constants()
+  {
+    assert_eq!(WGS84_FLATTENING, 1.0 / 298.257223563);
+    assert_eq!(WGS84_SEMI_MINOR_AXIS, WGS84_SEMI_MAJOR_AXIS * (1.0 - WGS84_FLATTENING));
+    assert!((WGS84_SEMI_MINOR_AXIS - 6356752.314245).abs() < 1e-6);
+    assert!((WGS84_ECCENTRICITY_SQUARED - 0.00669437999014).abs() < 1e-14);
+  }
+}
@@ -1,9 +1,15 @@
+use std::f64::consts::{FRAC_PI_2, FRAC_PI_4, PI};
 use std::fmt::{Display};
 use std::ops::{Add, Div, Mul, Sub};
+use std::str::FromStr;
+use std::time::Duration;
 use float_cmp::approx_eq;
 use crate::positioning::constants as Constants;
+use crate::positioning::ellipsoid::Ellipsoid;
 use crate::positioning::errors::PositioningError;
-use crate::positioning::utility::{CoordinateField};
+use crate::positioning::georectangle::GeoRectangle;
+use crate::positioning::mercator;
+use crate::positioning::utility::{normalize_longitude, CardinalDirection, CoordinateField, DistanceUnit};
 use crate::positioning::utility::CoordinateFieldType::{Latitude, Longitude};
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -22,15 +28,37 @@ pub struct GeoCoordinate
   pub altitude: Option<f32>
 }
 
+/// Latitude and longitude rounded to a fixed number of decimal places, usable as a hash key.
+/// Altitude is not part of the key.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct QuantizedCoordinate
+{
+  latitude: i64,
+  longitude: i64,
+  decimals: u8
+}
+
+impl QuantizedCoordinate
+{
+  pub fn decimals(&self) -> u8 { self.decimals }
+
+  pub fn to_coordinate(&self) -> GeoCoordinate
+  {
+    let scale = 10f64.powi(self.decimals as i32);
+    GeoCoordinate::new(self.latitude as f64 / scale, self.longitude as f64 / scale, None)
+  }
+}
+
 impl PartialEq for GeoCoordinate
 {
   fn eq(&self, other: &Self) -> bool
   {
-    approx_eq!(f64, self.latitude, other.latitude, epsilon = 0.0000003) &&
-      approx_eq!(f64, self.longitude, other.longitude, epsilon = 0.0000003) &&
-      ((other.altitude.is_none() && other.altitude.is_none()) ||
-        approx_eq!(f32, self.altitude.unwrap_or(1.0), other.altitude.unwrap_or(-1.0),
-        epsilon = 0.0000003))
+    const EPSILON: f64 = 0.0000003;
+
+    match (self.altitude, other.altitude) {
+      (Some(_), Some(_)) => self.approx_eq_3d(other, EPSILON, EPSILON as f32),
+      _ => self.approx_eq(other, EPSILON)
+    }
   }
 }
 
@@ -45,6 +73,81 @@ impl Display for GeoCoordinate
   }
 }
 
+/// Accepts decimal degrees (`60.5, 30.25`) or degree-minute-second notation with hemisphere markers
+/// (`60°30'15"N 30°15'W`), each optionally followed by an altitude in meters.
+impl FromStr for GeoCoordinate
+{
+  type Err = PositioningError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err>
+  {
+    let error = |reason: &str| PositioningError::ParseError(format!("{}: {}", reason, s));
+    let parse_number = |token: &str| token.trim().parse::<f64>().map_err(|_| error("invalid number"));
+
+    let ret = if s.contains(['N', 'S', 'E', 'W']) {
+      let (mut latitude, mut longitude, mut buffer) = (None, None, String::new());
+      for c in s.chars() {
+        match c {
+          'N' | 'S' | 'E' | 'W' => {
+            let value = Self::parse_dms(&buffer).ok_or_else(|| error("invalid degree-minute-second value"))?;
+            let slot = if matches!(c, 'N' | 'S') { &mut latitude } else { &mut longitude };
+            if slot.is_some() { return Err(error("duplicate hemisphere")) }
+            *slot = Some(if matches!(c, 'S' | 'W') { -value } else { value });
+            buffer.clear();
+          },
+          _ => buffer.push(c)
+        }
+      }
+      let (Some(latitude), Some(longitude)) = (latitude, longitude) else {
+        return Err(error("expected one N/S and one E/W component"))
+      };
+      let rest = buffer.trim().trim_start_matches(',');
+      let altitude = if rest.trim().is_empty() { None } else { Some(parse_number(rest)? as f32) };
+      GeoCoordinate::new(latitude, longitude, altitude)
+    } else {
+      let values = s
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|t| !t.is_empty())
+        .map(parse_number)
+        .collect::<Result<Vec<f64>, PositioningError>>()?;
+      match values[..] {
+        [latitude, longitude] => GeoCoordinate::new(latitude, longitude, None),
+        [latitude, longitude, altitude] => GeoCoordinate::new(latitude, longitude, Some(altitude as f32)),
+        _ => return Err(error("expected latitude, longitude and optional altitude"))
+      }
+    };
+    if !ret.valid() { return Err(error("coordinate out of range")) }
+    Ok(ret)
+  }
+}
+
+/// Tuples are in latitude-longitude order, not the longitude-latitude order used by GeoJSON.
+impl From<(f64, f64)> for GeoCoordinate
+{
+  fn from((latitude, longitude): (f64, f64)) -> Self
+  {
+    Self::new(latitude, longitude, None)
+  }
+}
+
+/// Latitude, longitude and altitude, in that order.
+impl From<(f64, f64, f32)> for GeoCoordinate
+{
+  fn from((latitude, longitude, altitude): (f64, f64, f32)) -> Self
+  {
+    Self::new(latitude, longitude, Some(altitude))
+  }
+}
+
+/// Produces `(latitude, longitude)`, dropping the altitude.
+impl From<GeoCoordinate> for (f64, f64)
+{
+  fn from(coordinate: GeoCoordinate) -> Self
+  {
+    (coordinate.latitude, coordinate.longitude)
+  }
+}
+
 impl Default for GeoCoordinate
 {
   fn default() -> Self
@@ -60,6 +163,63 @@ impl GeoCoordinate
     Self { latitude, longitude, altitude }
   }
 
+  /// Folds latitude over the poles and wraps longitude into [-180, 180), so 180° becomes -180°.
+  pub fn new_normalized(latitude: f64, longitude: f64, altitude: Option<f32>) -> Self
+  {
+    let mut lat = (latitude + 180.0).rem_euclid(360.0) - 180.0;
+    let mut lon = longitude;
+    if lat > 90.0 {
+      lat = 180.0 - lat;
+      lon += 180.0;
+    } else if lat < -90.0 {
+      lat = -180.0 - lat;
+      lon += 180.0;
+    }
+    let lon = normalize_longitude(lon);
+    Self { latitude: lat, longitude: if lon == 180.0 { -180.0 } else { lon }, altitude }
+  }
+
+  pub fn from_radians(latitude: f64, longitude: f64, altitude: Option<f32>) -> Self
+  {
+    Self::new(latitude.to_degrees(), longitude.to_degrees(), altitude)
+  }
+
+  pub fn to_radians(&self) -> (f64, f64) { (self.latitude.to_radians(), self.longitude.to_radians()) }
+
+  /// Longitudes are compared modulo 360, so 180 and -180 are the same meridian.
+  pub fn approx_eq(&self, other: &GeoCoordinate, epsilon_degrees: f64) -> bool
+  {
+    let delta = (self.longitude - other.longitude).rem_euclid(360.0);
+    approx_eq!(f64, self.latitude, other.latitude, epsilon = epsilon_degrees) &&
+      (approx_eq!(f64, self.longitude, other.longitude, epsilon = epsilon_degrees)
+        || approx_eq!(f64, delta, 0.0, epsilon = epsilon_degrees)
+        || approx_eq!(f64, delta, 360.0, epsilon = epsilon_degrees))
+  }
+
+  /// Like `approx_eq`, but also requires both altitudes to be absent, or present and within `epsilon_meters`.
+  pub fn approx_eq_3d(&self, other: &GeoCoordinate, epsilon_degrees: f64, epsilon_meters: f32) -> bool
+  {
+    self.approx_eq(other, epsilon_degrees) && match (self.altitude, other.altitude) {
+      (None, None) => true,
+      (Some(a), Some(b)) => approx_eq!(f32, a, b, epsilon = epsilon_meters),
+      _ => false
+    }
+  }
+
+  pub fn latitude(&self) -> f64 { self.latitude }
+  pub fn longitude(&self) -> f64 { self.longitude }
+  pub fn altitude(&self) -> Option<f32> { self.altitude }
+
+  pub fn with_altitude(self, altitude: f32) -> GeoCoordinate
+  {
+    GeoCoordinate { altitude: Some(altitude), ..self }
+  }
+
+  pub fn without_altitude(self) -> GeoCoordinate
+  {
+    GeoCoordinate { altitude: None, ..self }
+  }
+
   pub fn coordinate_type(&self) -> GeoCoordinateType
   {
     if self.latitude.valid(Latitude)
@@ -76,11 +236,124 @@ impl GeoCoordinate
     self.coordinate_type() != GeoCoordinateType::InvalidCoordinate
   }
 
+  /// Zoom levels above `MAX_ZOOM` (22) are compared at `MAX_ZOOM`.
+  pub fn same_tile_as(&self, other: &GeoCoordinate, zoom: u8) -> bool
+  {
+    if !self.valid() || !other.valid() { return false }
+    let zoom = zoom.min(mercator::MAX_ZOOM);
+    mercator::tile(self.latitude, self.longitude, zoom) == mercator::tile(other.latitude, other.longitude, zoom)
+  }
+
+  pub fn round_to_precision(&self, decimals: u8) -> GeoCoordinate
+  {
+    let scale = 10f64.powi(decimals as i32);
+    GeoCoordinate::new(
+      (self.latitude * scale).round() / scale,
+      (self.longitude * scale).round() / scale,
+      self.altitude.map(|a| (a * 100.0).round() / 100.0)
+    )
+  }
+
+  pub fn quantize(&self, decimals: u8) -> QuantizedCoordinate
+  {
+    let scale = 10f64.powi(decimals as i32);
+    QuantizedCoordinate {
+      latitude: (self.latitude * scale).round() as i64,
+      longitude: (self.longitude * scale).round() as i64,
+      decimals
+    }
+  }
+
+  /// Fixed 20-byte little-endian layout: latitude (f64), longitude (f64), altitude (f32).
+  /// A missing altitude is stored as NaN, and a NaN altitude decodes back to `None`.
+  pub fn to_bytes(&self) -> [u8; 20]
+  {
+    let mut bytes = [0u8; 20];
+    bytes[0..8].copy_from_slice(&self.latitude.to_le_bytes());
+    bytes[8..16].copy_from_slice(&self.longitude.to_le_bytes());
+    bytes[16..20].copy_from_slice(&self.altitude.unwrap_or(f32::NAN).to_le_bytes());
+    bytes
+  }
+
+  /// Inverse of `to_bytes`. The decoded coordinate is not validated.
+  pub fn from_bytes(bytes: &[u8; 20]) -> GeoCoordinate
+  {
+    let altitude = f32::from_le_bytes(bytes[16..20].try_into().unwrap());
+    GeoCoordinate::new(
+      f64::from_le_bytes(bytes[0..8].try_into().unwrap()),
+      f64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+      if altitude.is_nan() { None } else { Some(altitude) }
+    )
+  }
+
+  pub fn to_dms_string(&self) -> String
+  {
+    self.to_dms_string_with_precision(0)
+  }
+
+  pub fn to_dms_string_with_precision(&self, seconds_decimals: u8) -> String
+  {
+    if !self.valid() { return "invalid".to_string() }
+    let format = |value: f64, degree_digits: usize, positive: char, negative: char| {
+      let scale = 10u64.pow(seconds_decimals as u32);
+      let units = (value.abs() * 3600.0 * scale as f64).round() as u64;
+      let (degrees, minutes) = (units / (3600 * scale), units / (60 * scale) % 60);
+      let seconds = (units % (60 * scale)) as f64 / scale as f64;
+      let width = if seconds_decimals > 0 { 3 + seconds_decimals as usize } else { 2 };
+      format!("{:0dw$}°{:02}'{:0sw$.sp$}\"{}", degrees, minutes, seconds,
+        if value < 0.0 { negative } else { positive },
+        dw = degree_digits, sw = width, sp = seconds_decimals as usize)
+    };
+    format!("{} {}", format(self.latitude, 2, 'N', 'S'), format(self.longitude, 3, 'E', 'W'))
+  }
+
+  /// Treats the rectangle as a repeating tile: longitude wraps modulo the rectangle width,
+  /// latitude is clamped to its edges.
+  pub fn wrapped_into(&self, rect: &GeoRectangle) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !rect.valid() { return Err(PositioningError::InvalidGeorectangle(rect.clone())) }
+    let (left, width) = (rect.top_left().longitude, rect.width());
+    let longitude = if width > 0.0 { left + (self.longitude - left).rem_euclid(width) } else { left };
+    Ok(GeoCoordinate::new(
+      self.latitude.clamp(rect.bottom_right().latitude, rect.top_left().latitude),
+      normalize_longitude(longitude),
+      self.altitude
+    ))
+  }
+
+  pub fn clamp_to_rect(&self, rect: &GeoRectangle) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !rect.valid() { return Err(PositioningError::InvalidGeorectangle(rect.clone())) }
+    let (left, width) = (rect.top_left().longitude, rect.width());
+    let offset = (self.longitude - left).rem_euclid(360.0);
+    let longitude = if offset <= width { self.longitude }
+      else if offset - width < 360.0 - offset { rect.bottom_right().longitude }
+      else { left };
+    Ok(GeoCoordinate::new(
+      self.latitude.clamp(rect.bottom_right().latitude, rect.top_left().latitude),
+      longitude,
+      self.altitude
+    ))
+  }
+
   pub fn azimuth_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
   {
     if !self.valid() { return Err(PositioningError::InvalidCoordinate(self.clone())) }
     if !other.valid() { return Err(PositioningError::InvalidCoordinate(other.clone())) }
+    Ok(self.spherical_azimuth_to(other))
+  }
+
+  pub fn bearing_and_distance_to(&self, other: &GeoCoordinate) -> Result<(f32, f32), PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    Ok((self.spherical_azimuth_to(other), self.spherical_distance_to(other)))
+  }
 
+  fn spherical_azimuth_to(&self, other: &GeoCoordinate) -> f32
+  {
     let d_lon = (other.longitude - self.longitude).to_radians();
     let azimuth = d_lon
       .sin()
@@ -107,15 +380,38 @@ impl GeoCoordinate
     )
     ).to_degrees()
       .add(360.0);
-    Ok(((azimuth.trunc() + 360.0) as i32 % 360) as f32 + azimuth.fract() as f32)
+    ((azimuth.trunc() + 360.0) as i32 % 360) as f32 + azimuth.fract() as f32
+  }
+
+  pub fn azimuth_to_ellipsoidal(&self, other: &GeoCoordinate, ellipsoid: Ellipsoid) -> Result<f32, PositioningError>
+  {
+    if ellipsoid == Ellipsoid::Sphere { return self.azimuth_to(other) }
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    Ok(self.vincenty_inverse(other, ellipsoid)?.1 as f32)
   }
 
   pub fn distance_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    self.distance_to_on(other, Ellipsoid::Sphere)
+  }
+
+  pub fn is_near(&self, other: &GeoCoordinate, radius_meters: f32) -> Result<bool, PositioningError>
+  {
+    Ok(self.distance_to(other)? <= radius_meters)
+  }
+
+  pub fn distance_to_on(&self, other: &GeoCoordinate, model: Ellipsoid) -> Result<f32, PositioningError>
   {
     if !self.valid() { return Err(PositioningError::InvalidCoordinate(self.clone())) }
     if !other.valid() { return Err(PositioningError::InvalidCoordinate(other.clone())) }
+    if model != Ellipsoid::Sphere { return Ok(self.vincenty_inverse(other, model)?.0 as f32) }
+    Ok(self.spherical_distance_to(other))
+  }
 
-    let res = Constants::EARTH_MEAN_RADIUS
+  fn spherical_distance_to(&self, other: &GeoCoordinate) -> f32
+  {
+    Constants::EARTH_MEAN_RADIUS
       .mul(self.latitude
         .to_radians()
         .cos()
@@ -134,13 +430,92 @@ impl GeoCoordinate
         .powi(2)
       ).sqrt()
         .asin() as f32
-      ).mul(2.0f32);
-    Ok(res)
+      ).mul(2.0f32)
+  }
+
+  pub fn distances_to_many(&self, others: &[GeoCoordinate]) -> Result<Vec<f32>, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+
+    let cos_lat = self.latitude.to_radians().cos();
+    others
+      .iter()
+      .enumerate()
+      .map(|(i, other)| {
+        if !other.valid() { return Err(PositioningError::InvalidCoordinateAt(i, *other)) }
+        let h = cos_lat
+          .mul(other.latitude.to_radians().cos())
+          .mul((other.longitude - self.longitude).to_radians().div(2.0).sin().powi(2))
+          .add((other.latitude - self.latitude).to_radians().div(2.0).sin().powi(2));
+        Ok(Constants::EARTH_MEAN_RADIUS.mul(h.sqrt().asin() as f32).mul(2.0f32))
+      }).collect()
+  }
+
+  /// Partial derivatives of `distance_to` with respect to the other coordinate's latitude and longitude,
+  /// in meters per radian.
+  pub fn distance_gradient_to(&self, other: &GeoCoordinate) -> Result<(f64, f64), PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    if self.central_angle_to(other) < 1e-12 {
+      return Err(PositioningError::InvalidArgument("distance gradient is undefined for coincident points"))
+    }
+    let (lat1, lon1) = self.to_radians();
+    let (lat2, lon2) = other.to_radians();
+    let d_lon = lon1 - lon2;
+    let back_azimuth = (d_lon.sin() * lat1.cos()).atan2(lat2.cos() * lat1.sin() - lat2.sin() * lat1.cos() * d_lon.cos());
+    let radius = Constants::EARTH_MEAN_RADIUS as f64;
+    Ok((-radius * back_azimuth.cos(), -radius * lat2.cos() * back_azimuth.sin()))
+  }
+
+  pub fn distance_to_3d(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    let surface = self.distance_to(other)?;
+    let d_alt = other.altitude.unwrap_or(0.0) - self.altitude.unwrap_or(0.0);
+    Ok(surface.hypot(d_alt))
+  }
+
+  pub fn elevation_angle_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    let (Some(from), Some(to)) = (self.altitude, other.altitude) else {
+      return Err(PositioningError::InvalidArgument("elevation angle requires altitude on both coordinates"))
+    };
+    let radius = Constants::EARTH_MEAN_RADIUS as f64;
+    let (r1, r2) = (radius + from as f64, radius + to as f64);
+    let theta = self.central_angle_to(other);
+    Ok((r2 * theta.cos() - r1).atan2(r2 * theta.sin()).to_degrees() as f32)
+  }
+
+  pub fn distance_to_in(&self, other: &GeoCoordinate, unit: DistanceUnit) -> Result<f32, PositioningError>
+  {
+    Ok(unit.from_meters(self.distance_to(other)?))
+  }
+
+  pub fn cardinal_direction_to(&self, other: &GeoCoordinate) -> Result<CardinalDirection, PositioningError>
+  {
+    Ok(CardinalDirection::from_degrees(self.azimuth_to(other)?))
+  }
+
+  pub fn describe_relative_to(&self, other: &GeoCoordinate, unit: DistanceUnit, precision: usize)
+    -> Result<String, PositioningError>
+  {
+    let distance = other.distance_to_in(self, unit)?;
+    let direction = other.cardinal_direction_to(self)?;
+    Ok(format!("{:.*} {} {}", precision, distance, unit.symbol(), direction.name()))
   }
 
   pub fn at_distance_and_azimuth(&self, distance: f32, azimuth: f32) -> Result<GeoCoordinate, PositioningError>
+  {
+    self.at_distance_and_azimuth_on(distance, azimuth, Ellipsoid::Sphere)
+  }
+
+  pub fn at_distance_and_azimuth_on(&self, distance: f32, azimuth: f32, model: Ellipsoid)
+    -> Result<GeoCoordinate, PositioningError>
   {
     if !self.valid() { return Err(PositioningError::InvalidCoordinate(self.clone())); }
+    if model != Ellipsoid::Sphere { return self.vincenty_direct(distance as f64, azimuth as f64, model) }
 
     let ratio = distance as f64 / Constants::EARTH_MEAN_RADIUS as f64;
     let lat = (self.latitude
@@ -168,6 +543,328 @@ impl GeoCoordinate
       self.altitude
     ))
   }
+
+  /// Angle in degrees from true north to UTM grid north at this coordinate, positive east of the zone's
+  /// central meridian in the northern hemisphere. Uses the standard 6° zones without the Norway/Svalbard exceptions.
+  pub fn meridian_convergence(&self) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    let zone = (((self.longitude + 180.0) / 6.0).floor() as i32).clamp(0, 59);
+    let central_meridian = zone as f64 * 6.0 - 177.0;
+    let delta = (self.longitude - central_meridian).to_radians();
+    Ok((delta.tan() * self.latitude.to_radians().sin()).atan().to_degrees() as f32)
+  }
+
+  pub fn dead_reckon(&self, speed_meters_per_sec: f32, heading_degrees: f32, elapsed: Duration)
+    -> Result<GeoCoordinate, PositioningError>
+  {
+    if !speed_meters_per_sec.is_finite() { return Err(PositioningError::InvalidArgument("speed must be finite")) }
+    self.at_distance_and_azimuth(speed_meters_per_sec * elapsed.as_secs_f32(), heading_degrees)
+  }
+
+  pub fn rhumb_azimuth_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+
+    let d_psi = Self::mercator_latitude_difference(self.latitude, other.latitude);
+    let d_lon = Self::shortest_longitude_difference(self.longitude, other.longitude);
+    Ok(d_lon.atan2(d_psi).to_degrees().rem_euclid(360.0) as f32)
+  }
+
+  pub fn rhumb_distance_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+
+    let d_lat = (other.latitude - self.latitude).to_radians();
+    let d_psi = Self::mercator_latitude_difference(self.latitude, other.latitude);
+    let q = if d_psi.abs() > 1e-12 { d_lat / d_psi } else { self.latitude.to_radians().cos() };
+    let d_lon = Self::shortest_longitude_difference(self.longitude, other.longitude);
+    Ok(((d_lat * d_lat + q * q * d_lon * d_lon).sqrt() * Constants::EARTH_MEAN_RADIUS as f64) as f32)
+  }
+
+  pub fn rhumb_destination(&self, distance: f32, bearing: f32) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+
+    let ratio = distance as f64 / Constants::EARTH_MEAN_RADIUS as f64;
+    let theta = (bearing as f64).to_radians();
+    let lat1 = self.latitude.to_radians();
+    let d_lat = ratio * theta.cos();
+    let mut lat2 = lat1 + d_lat;
+    if lat2.abs() > FRAC_PI_2 {
+      lat2 = if lat2 > 0.0 { PI - lat2 } else { -PI - lat2 };
+    }
+    let d_psi = Self::mercator_latitude_difference(self.latitude, lat2.to_degrees());
+    let q = if d_psi.abs() > 1e-12 { d_lat / d_psi } else { lat1.cos() };
+    let d_lon = ratio * theta.sin() / q;
+    Ok(GeoCoordinate::new(
+      lat2.to_degrees(),
+      normalize_longitude(self.longitude + d_lon.to_degrees()),
+      self.altitude
+    ))
+  }
+
+  /// Unit vector normal to the ellipsoid (sphere) at this position, in the Earth-centered frame
+  /// with z towards the north pole and x towards (0°, 0°).
+  pub fn to_n_vector(&self) -> (f64, f64, f64)
+  {
+    let (lat, lon) = self.to_radians();
+    (lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin())
+  }
+
+  pub fn from_n_vector(n_vector: (f64, f64, f64), altitude: Option<f32>) -> Self
+  {
+    let (x, y, z) = n_vector;
+    Self::from_radians(z.atan2((x * x + y * y).sqrt()), y.atan2(x), altitude)
+  }
+
+  pub fn n_vector_distance_to(&self, other: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    let (a, b) = (self.to_n_vector(), other.to_n_vector());
+    let cross = (a.1 * b.2 - a.2 * b.1, a.2 * b.0 - a.0 * b.2, a.0 * b.1 - a.1 * b.0);
+    let sin = (cross.0 * cross.0 + cross.1 * cross.1 + cross.2 * cross.2).sqrt();
+    let cos = a.0 * b.0 + a.1 * b.1 + a.2 * b.2;
+    Ok((sin.atan2(cos) * Constants::EARTH_MEAN_RADIUS as f64) as f32)
+  }
+
+  pub fn midpoint(&self, other: &GeoCoordinate) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+    let (a, b) = (self.to_n_vector(), other.to_n_vector());
+    let sum = (a.0 + b.0, a.1 + b.1, a.2 + b.2);
+    if (sum.0 * sum.0 + sum.1 * sum.1 + sum.2 * sum.2).sqrt() < 1e-12 {
+      return Err(PositioningError::InvalidArgument("midpoint of antipodal points is undefined"))
+    }
+    let altitude = self.altitude.zip(other.altitude).map(|(x, y)| (x + y) / 2.0);
+    Ok(Self::from_n_vector(sum, altitude))
+  }
+
+  pub fn antipode(&self) -> GeoCoordinate
+  {
+    GeoCoordinate::new(-self.latitude, normalize_longitude(self.longitude + 180.0), self.altitude)
+  }
+
+  /// Nearest point to `self` on the rhumb line from `a` to `b`, clamped to the segment.
+  pub fn project_onto_rhumb_segment(&self, a: &GeoCoordinate, b: &GeoCoordinate) -> Result<GeoCoordinate, PositioningError>
+  {
+    const ITERATIONS: usize = 100;
+
+    for c in [self, a, b] {
+      if !c.valid() { return Err(PositioningError::InvalidCoordinate(*c)) }
+    }
+    if a.latitude.abs() == 90.0 || b.latitude.abs() == 90.0 {
+      return Err(PositioningError::InvalidArgument("rhumb line through a pole is undefined"))
+    }
+    let d_psi = Self::mercator_latitude_difference(a.latitude, b.latitude);
+    let d_lon = Self::shortest_longitude_difference(a.longitude, b.longitude);
+    let psi_a = (a.latitude.to_radians() / 2.0 + FRAC_PI_4).tan().ln();
+    let at = |t: f64| {
+      let longitude = a.longitude + (d_lon * t).to_degrees();
+      GeoCoordinate::new(
+        (2.0 * (psi_a + d_psi * t).exp().atan() - FRAC_PI_2).to_degrees(),
+        normalize_longitude(longitude),
+        None
+      )
+    };
+
+    let (mut low, mut high) = (0.0f64, 1.0f64);
+    let ratio = (5.0f64.sqrt() - 1.0) / 2.0;
+    for _ in 0..ITERATIONS {
+      let (m1, m2) = (high - ratio * (high - low), low + ratio * (high - low));
+      if self.central_angle_to(&at(m1)) <= self.central_angle_to(&at(m2)) { high = m2 } else { low = m1 }
+    }
+    Ok(at((low + high) / 2.0))
+  }
+
+  pub fn interpolate(&self, other: &GeoCoordinate, fraction: f64) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !other.valid() { return Err(PositioningError::InvalidCoordinate(*other)) }
+
+    let altitude = match (self.altitude, other.altitude) {
+      (Some(a), Some(b)) => Some(a + (b - a) * fraction as f32),
+      _ => None
+    };
+    let delta = self.central_angle_to(other);
+    if delta.abs() < 1e-12 { return Ok(GeoCoordinate::new(self.latitude, self.longitude, altitude)) }
+    if self.antipode().central_angle_to(other) < 1e-9 {
+      return Err(PositioningError::InvalidArgument("great circle between antipodal points is undefined"))
+    }
+
+    let (lat1, lon1) = (self.latitude.to_radians(), self.longitude.to_radians());
+    let (lat2, lon2) = (other.latitude.to_radians(), other.longitude.to_radians());
+    let a = ((1.0 - fraction) * delta).sin() / delta.sin();
+    let b = (fraction * delta).sin() / delta.sin();
+    let x = a * lat1.cos() * lon1.cos() + b * lat2.cos() * lon2.cos();
+    let y = a * lat1.cos() * lon1.sin() + b * lat2.cos() * lon2.sin();
+    let z = a * lat1.sin() + b * lat2.sin();
+    Ok(GeoCoordinate::new(
+      z.atan2((x * x + y * y).sqrt()).to_degrees(),
+      y.atan2(x).to_degrees(),
+      altitude
+    ))
+  }
+
+  /// Instantaneous great-circle bearing at `fraction` (0..=1) of the way from `self` to `end`.
+  /// Equals `azimuth_to(end)` at 0 and the final bearing on arrival at 1.
+  pub fn tangent_bearing_at(&self, end: &GeoCoordinate, fraction: f64) -> Result<f32, PositioningError>
+  {
+    if !(0.0..=1.0).contains(&fraction) { return Err(PositioningError::InvalidArgument("fraction must be within 0..=1")) }
+    if self.central_angle_to(end) < 1e-12 {
+      return Err(PositioningError::InvalidArgument("bearing between coincident points is undefined"))
+    }
+    let point = self.interpolate(end, fraction)?;
+    if fraction <= 0.5 { return Ok(point.spherical_azimuth_to(end)) }
+    Ok((point.spherical_azimuth_to(self) + 180.0) % 360.0)
+  }
+
+  pub fn great_circle_fraction(&self, start: &GeoCoordinate, end: &GeoCoordinate) -> Result<f64, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidCoordinate(*self)) }
+    if !start.valid() { return Err(PositioningError::InvalidCoordinate(*start)) }
+    if !end.valid() { return Err(PositioningError::InvalidCoordinate(*end)) }
+
+    let total = start.central_angle_to(end);
+    if total.abs() < 1e-12 { return Ok(0.0) }
+    let to_self = start.central_angle_to(self);
+    let theta = (start.azimuth_to(self)? as f64 - start.azimuth_to(end)? as f64).to_radians();
+    let cross_track = (to_self.sin() * theta.sin()).asin();
+    let along_track = (to_self.cos() / cross_track.cos()).clamp(-1.0, 1.0).acos();
+    Ok(along_track.copysign(theta.cos()) / total)
+  }
+
+  fn parse_dms(component: &str) -> Option<f64>
+  {
+    let mut rest = component.trim().trim_start_matches(',').trim();
+    let (mut value, mut any) = (0.0, false);
+    for (marker, divisor) in [('°', 1.0), ('\'', 60.0), ('"', 3600.0)] {
+      if let Some((head, tail)) = rest.split_once(marker) {
+        let part = head.trim().parse::<f64>().ok()?;
+        if part < 0.0 || (divisor > 1.0 && part >= 60.0) { return None }
+        value += part / divisor;
+        rest = tail.trim();
+        any = true;
+      }
+    }
+    if !rest.is_empty() {
+      if any { return None }
+      value = rest.parse::<f64>().ok().filter(|v| *v >= 0.0)?;
+    } else if !any { return None }
+    Some(value)
+  }
+
+  fn central_angle_to(&self, other: &GeoCoordinate) -> f64
+  {
+    let h = ((other.latitude - self.latitude).to_radians() / 2.0).sin().powi(2)
+      + self.latitude.to_radians().cos() * other.latitude.to_radians().cos()
+      * ((other.longitude - self.longitude).to_radians() / 2.0).sin().powi(2);
+    2.0 * h.sqrt().min(1.0).asin()
+  }
+
+  // returns (distance, initial azimuth in degrees)
+  fn vincenty_inverse(&self, other: &GeoCoordinate, model: Ellipsoid) -> Result<(f64, f64), PositioningError>
+  {
+    const MAX_ITERATIONS: usize = 200;
+
+    let (a, f, b) = (model.semi_major_axis(), model.flattening(), model.semi_minor_axis());
+    let l = (other.longitude - self.longitude).to_radians();
+    let u1 = ((1.0 - f) * self.latitude.to_radians().tan()).atan();
+    let u2 = ((1.0 - f) * other.latitude.to_radians().tan()).atan();
+    let (sin_u1, cos_u1) = u1.sin_cos();
+    let (sin_u2, cos_u2) = u2.sin_cos();
+
+    let mut lambda = l;
+    let (mut sin_sigma, mut cos_sigma, mut sigma) = (0.0, 0.0, 0.0);
+    let (mut cos_sq_alpha, mut cos_2sigma_m) = (0.0, 0.0);
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+      let (sin_lambda, cos_lambda) = lambda.sin_cos();
+      sin_sigma = ((cos_u2 * sin_lambda).powi(2)
+        + (cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda).powi(2)).sqrt();
+      if sin_sigma == 0.0 { return Ok((0.0, 0.0)) }
+      cos_sigma = sin_u1 * sin_u2 + cos_u1 * cos_u2 * cos_lambda;
+      sigma = sin_sigma.atan2(cos_sigma);
+      let sin_alpha = cos_u1 * cos_u2 * sin_lambda / sin_sigma;
+      cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+      cos_2sigma_m = if cos_sq_alpha != 0.0 { cos_sigma - 2.0 * sin_u1 * sin_u2 / cos_sq_alpha } else { 0.0 };
+      let c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+      let previous = lambda;
+      lambda = l + (1.0 - c) * f * sin_alpha
+        * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+      if (lambda - previous).abs() < 1e-12 { converged = true; break }
+    }
+    if !converged { return Err(PositioningError::ConvergenceFailure { iterations: MAX_ITERATIONS }) }
+
+    let u_sq = cos_sq_alpha * (a * a - b * b) / (b * b);
+    let big_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+    let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+    let delta_sigma = big_b * sin_sigma * (cos_2sigma_m + big_b / 4.0
+      * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+        - big_b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma.powi(2)) * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+    let (sin_lambda, cos_lambda) = lambda.sin_cos();
+    let azimuth = (cos_u2 * sin_lambda).atan2(cos_u1 * sin_u2 - sin_u1 * cos_u2 * cos_lambda);
+    Ok((b * big_a * (sigma - delta_sigma), azimuth.to_degrees().rem_euclid(360.0)))
+  }
+
+  fn vincenty_direct(&self, distance: f64, azimuth: f64, model: Ellipsoid) -> Result<GeoCoordinate, PositioningError>
+  {
+    const MAX_ITERATIONS: usize = 200;
+
+    let (a, f, b) = (model.semi_major_axis(), model.flattening(), model.semi_minor_axis());
+    let (sin_alpha1, cos_alpha1) = azimuth.to_radians().sin_cos();
+    let tan_u1 = (1.0 - f) * self.latitude.to_radians().tan();
+    let cos_u1 = 1.0 / (1.0 + tan_u1 * tan_u1).sqrt();
+    let sin_u1 = tan_u1 * cos_u1;
+    let sigma1 = tan_u1.atan2(cos_alpha1);
+    let sin_alpha = cos_u1 * sin_alpha1;
+    let cos_sq_alpha = 1.0 - sin_alpha * sin_alpha;
+    let u_sq = cos_sq_alpha * (a * a - b * b) / (b * b);
+    let big_a = 1.0 + u_sq / 16384.0 * (4096.0 + u_sq * (-768.0 + u_sq * (320.0 - 175.0 * u_sq)));
+    let big_b = u_sq / 1024.0 * (256.0 + u_sq * (-128.0 + u_sq * (74.0 - 47.0 * u_sq)));
+
+    let mut sigma = distance / (b * big_a);
+    let (mut sin_sigma, mut cos_sigma, mut cos_2sigma_m) = (0.0, 0.0, 0.0);
+    let mut converged = false;
+    for _ in 0..MAX_ITERATIONS {
+      cos_2sigma_m = (2.0 * sigma1 + sigma).cos();
+      (sin_sigma, cos_sigma) = sigma.sin_cos();
+      let delta_sigma = big_b * sin_sigma * (cos_2sigma_m + big_b / 4.0
+        * (cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))
+          - big_b / 6.0 * cos_2sigma_m * (-3.0 + 4.0 * sin_sigma.powi(2)) * (-3.0 + 4.0 * cos_2sigma_m.powi(2))));
+      let previous = sigma;
+      sigma = distance / (b * big_a) + delta_sigma;
+      if (sigma - previous).abs() < 1e-12 { converged = true; break }
+    }
+    if !converged { return Err(PositioningError::ConvergenceFailure { iterations: MAX_ITERATIONS }) }
+
+    let tmp = sin_u1 * sin_sigma - cos_u1 * cos_sigma * cos_alpha1;
+    let lat = (sin_u1 * cos_sigma + cos_u1 * sin_sigma * cos_alpha1)
+      .atan2((1.0 - f) * (sin_alpha * sin_alpha + tmp * tmp).sqrt());
+    let lambda = (sin_sigma * sin_alpha1).atan2(cos_u1 * cos_sigma - sin_u1 * sin_sigma * cos_alpha1);
+    let c = f / 16.0 * cos_sq_alpha * (4.0 + f * (4.0 - 3.0 * cos_sq_alpha));
+    let l = lambda - (1.0 - c) * f * sin_alpha
+      * (sigma + c * sin_sigma * (cos_2sigma_m + c * cos_sigma * (-1.0 + 2.0 * cos_2sigma_m.powi(2))));
+    Ok(GeoCoordinate::new(
+      lat.to_degrees(),
+      normalize_longitude(self.longitude + l.to_degrees()),
+      self.altitude
+    ))
+  }
+
+  fn mercator_latitude_difference(from: f64, to: f64) -> f64
+  {
+    ((to.to_radians() / 2.0 + FRAC_PI_4).tan() / (from.to_radians() / 2.0 + FRAC_PI_4).tan()).ln()
+  }
+
+  fn shortest_longitude_difference(from: f64, to: f64) -> f64
+  {
+    normalize_longitude(to - from).to_radians()
+  }
 }
 
 #[cfg(test)]
@@ -235,4 +932,520 @@ mod tests {
     assert_eq!(test_coord.at_distance_and_azimuth(d[3], az[2]).unwrap(), GeoCoordinate::new(60.390305136, 30.000000000, None));
     assert_eq!(test_coord.at_distance_and_azimuth(d[3], az[3]).unwrap(), GeoCoordinate::new(59.997697499, 30.780574051, None));
   }
+
+  #[test]
+  fn test_rhumb_distance_to()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let meridian = GeoCoordinate::new(59.0, 30.0, None);
+    assert_eq!(t.rhumb_distance_to(&meridian).unwrap().round(), t.distance_to(&meridian).unwrap().round());
+    let parallel = GeoCoordinate::new(60.0, 31.0, None);
+    assert!(t.rhumb_distance_to(&parallel).unwrap() > t.distance_to(&parallel).unwrap());
+    assert_eq!(t.rhumb_distance_to(&parallel).unwrap().round(), 55598.0);
+  }
+
+  #[test]
+  fn test_rhumb_azimuth_to()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    assert_eq!(t.rhumb_azimuth_to(&GeoCoordinate::new(60.0, 31.0, None)).unwrap(), 90.0);
+    assert_eq!(t.rhumb_azimuth_to(&GeoCoordinate::new(60.0, 29.0, None)).unwrap(), 270.0);
+    assert_eq!(t.rhumb_azimuth_to(&GeoCoordinate::new(59.0, 30.0, None)).unwrap(), 180.0);
+    let east = GeoCoordinate::new(0.0, 179.0, None);
+    assert_eq!(east.rhumb_azimuth_to(&GeoCoordinate::new(0.0, -179.0, None)).unwrap(), 90.0);
+    assert_eq!(east.rhumb_distance_to(&GeoCoordinate::new(0.0, -179.0, None)).unwrap().round(), 222390.0);
+  }
+
+  #[test]
+  fn test_rhumb_destination()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let target = GeoCoordinate::new(59.0, 29.0, None);
+    let destination = t
+      .rhumb_destination(t.rhumb_distance_to(&target).unwrap(), t.rhumb_azimuth_to(&target).unwrap())
+      .unwrap();
+    assert!(destination.distance_to(&target).unwrap() < 1.0);
+    let east = GeoCoordinate::new(0.0, 179.5, None)
+      .rhumb_destination(111195.0, 90.0)
+      .unwrap();
+    assert!((east.longitude + 179.5).abs() < 1e-3);
+  }
+
+  #[test]
+  fn test_interpolate()
+  {
+    let start = GeoCoordinate::new(60.0, 30.0, Some(0.0));
+    let end = GeoCoordinate::new(59.0, 29.0, Some(100.0));
+    assert_eq!(start.interpolate(&end, 0.0).unwrap(), start);
+    assert_eq!(start.interpolate(&end, 1.0).unwrap(), end);
+    let middle = start.interpolate(&end, 0.5).unwrap();
+    assert_eq!(middle.altitude, Some(50.0));
+    assert!((middle.distance_to(&start).unwrap() - middle.distance_to(&end).unwrap()).abs() < 0.01);
+  }
+
+  #[test]
+  fn test_great_circle_fraction()
+  {
+    let start = GeoCoordinate::new(60.0, 30.0, None);
+    let end = GeoCoordinate::new(59.0, 29.0, None);
+    for f in [0.0, 0.25, 0.5, 0.9, 1.0] {
+      let fraction = start
+        .interpolate(&end, f)
+        .unwrap()
+        .great_circle_fraction(&start, &end)
+        .unwrap();
+      assert!((fraction - f).abs() < 1e-6);
+    }
+  }
+
+  #[test]
+  fn test_describe_relative_to()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    assert_eq!(GeoCoordinate::new(60.0, 31.0, None).describe_relative_to(&t, DistanceUnit::Kilometers, 1).unwrap(),
+      "55.6 km east");
+    assert_eq!(GeoCoordinate::new(59.0, 29.0, None).describe_relative_to(&t, DistanceUnit::Kilometers, 2).unwrap(),
+      "124.69 km south-southwest");
+    assert_eq!(GeoCoordinate::new(59.0, 30.0, None).describe_relative_to(&t, DistanceUnit::Meters, 0).unwrap(),
+      "111195 m south");
+    assert!(GeoCoordinate::default().describe_relative_to(&t, DistanceUnit::Meters, 0).is_err());
+  }
+
+  #[test]
+  fn test_altitude_accessors()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    assert_eq!(t.latitude(), 60.0);
+    assert_eq!(t.longitude(), 30.0);
+    assert_eq!(t.altitude(), None);
+    let elevated = t.with_altitude(100.0);
+    assert_eq!(elevated.coordinate_type(), GeoCoordinateType::Coordinate3D);
+    assert_eq!(elevated.altitude(), Some(100.0));
+    assert_eq!(elevated.without_altitude().coordinate_type(), GeoCoordinateType::Coordinate2D);
+  }
+
+  #[test]
+  fn test_distance_to_3d()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, Some(0.0));
+    assert_eq!(t.distance_to_3d(&GeoCoordinate::new(60.0, 30.0, Some(1000.0))).unwrap(), 1000.0);
+    assert_eq!(t.distance_to_3d(&GeoCoordinate::new(60.0, 30.0, None)).unwrap(), 0.0);
+    let far = GeoCoordinate::new(59.0, 30.0, Some(100.0));
+    let d = t.distance_to_3d(&far).unwrap();
+    assert!(d > t.distance_to(&far).unwrap());
+    assert!(d - t.distance_to(&far).unwrap() < 0.1);
+  }
+
+  #[test]
+  fn test_new_normalized()
+  {
+    assert_eq!(GeoCoordinate::new_normalized(100.0, 200.0, None), GeoCoordinate::new(80.0, 20.0, None));
+    assert_eq!(GeoCoordinate::new_normalized(-100.0, 0.0, None), GeoCoordinate::new(-80.0, -180.0, None));
+    assert_eq!(GeoCoordinate::new_normalized(0.0, 180.0, None), GeoCoordinate::new(0.0, -180.0, None));
+    assert_eq!(GeoCoordinate::new_normalized(60.0, 390.0, Some(5.0)), GeoCoordinate::new(60.0, 30.0, Some(5.0)));
+    assert_eq!(GeoCoordinate::new_normalized(450.0, 0.0, None), GeoCoordinate::new(90.0, 0.0, None));
+    assert!(GeoCoordinate::new_normalized(-271.0, -725.0, None).valid());
+  }
+
+  #[test]
+  fn test_distance_to_on()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    for other in [
+      GeoCoordinate::new(60.0, 31.0, None),
+      GeoCoordinate::new(60.0, 29.0, None),
+      GeoCoordinate::new(59.0, 29.0, None),
+      GeoCoordinate::new(59.0, 30.0, None)
+    ] {
+      let sphere = t.distance_to_on(&other, Ellipsoid::Sphere).unwrap();
+      assert_eq!(sphere, t.distance_to(&other).unwrap());
+      let wgs84 = t.distance_to_on(&other, Ellipsoid::Wgs84).unwrap();
+      assert_ne!(wgs84, sphere);
+      assert!((wgs84 - sphere).abs() / sphere < 0.01);
+    }
+
+    let flinders_peak = GeoCoordinate::new(-37.951033417, 144.424867889, None);
+    let buninyong = GeoCoordinate::new(-37.652821139, 143.926495528, None);
+    assert!((flinders_peak.distance_to_on(&buninyong, Ellipsoid::Wgs84).unwrap() - 54972.27).abs() < 0.01);
+  }
+
+  #[test]
+  fn test_at_distance_and_azimuth_on()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    assert_eq!(t.at_distance_and_azimuth_on(10000.0, 90.0, Ellipsoid::Sphere).unwrap(),
+      t.at_distance_and_azimuth(10000.0, 90.0).unwrap());
+
+    let flinders_peak = GeoCoordinate::new(-37.951033417, 144.424867889, None);
+    let destination = flinders_peak
+      .at_distance_and_azimuth_on(54972.27, 306.86816, Ellipsoid::Wgs84)
+      .unwrap();
+    assert!((destination.latitude - -37.652821139).abs() < 1e-6);
+    assert!((destination.longitude - 143.926495528).abs() < 1e-6);
+  }
+
+  #[test]
+  fn test_azimuth_to_ellipsoidal()
+  {
+    let flinders_peak = GeoCoordinate::new(-37.951033417, 144.424867889, None);
+    let buninyong = GeoCoordinate::new(-37.652821139, 143.926495528, None);
+    let ellipsoidal = flinders_peak.azimuth_to_ellipsoidal(&buninyong, Ellipsoid::Wgs84).unwrap();
+    assert!((ellipsoidal - 306.86816).abs() < 1e-4);
+
+    let moscow = GeoCoordinate::new(55.7558, 37.6173, None);
+    let vladivostok = GeoCoordinate::new(43.1155, 131.8855, None);
+    let spherical = moscow.azimuth_to(&vladivostok).unwrap();
+    let ellipsoidal = moscow.azimuth_to_ellipsoidal(&vladivostok, Ellipsoid::Wgs84).unwrap();
+    assert_ne!(spherical, ellipsoidal);
+    assert!((spherical - ellipsoidal).abs() < 0.5);
+    assert_eq!(moscow.azimuth_to_ellipsoidal(&vladivostok, Ellipsoid::Sphere).unwrap(), spherical);
+  }
+
+  #[test]
+  fn test_bearing_and_distance_to()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let other = GeoCoordinate::new(59.0, 29.0, None);
+    assert_eq!(t.bearing_and_distance_to(&other).unwrap(),
+      (t.azimuth_to(&other).unwrap(), t.distance_to(&other).unwrap()));
+    assert!(t.bearing_and_distance_to(&GeoCoordinate::default()).is_err());
+  }
+
+  #[test]
+  fn test_distances_to_many()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let others: Vec<GeoCoordinate> = (0..100)
+      .map(|i| GeoCoordinate::new(-80.0 + i as f64 * 1.7, -170.0 + i as f64 * 3.3, None))
+      .collect();
+    let distances = t.distances_to_many(&others).unwrap();
+    assert_eq!(distances.len(), others.len());
+    for (other, distance) in others.iter().zip(distances) {
+      assert_eq!(distance, t.distance_to(other).unwrap());
+    }
+    let invalid = [GeoCoordinate::new(59.0, 29.0, None), GeoCoordinate::default()];
+    assert!(matches!(t.distances_to_many(&invalid), Err(PositioningError::InvalidCoordinateAt(1, _))));
+  }
+
+  #[test]
+  fn test_same_tile_as()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    assert!(t.same_tile_as(&GeoCoordinate::new(60.0001, 30.001, None), 10));
+    assert!(t.same_tile_as(&t, 22));
+    let west = GeoCoordinate::new(60.0, 30.23, None);
+    let east = GeoCoordinate::new(60.0, 30.24, None);
+    assert!(!west.same_tile_as(&east, 10));
+    assert!(west.same_tile_as(&east, 8));
+    assert!(!t.same_tile_as(&GeoCoordinate::default(), 0));
+    assert!(t.same_tile_as(&t, 32));
+    assert!(t.same_tile_as(&t, u8::MAX));
+    assert_eq!(west.same_tile_as(&east, 40), west.same_tile_as(&east, 22));
+  }
+
+  #[test]
+  fn test_quantize()
+  {
+    let a = GeoCoordinate::new(60.1234561, 30.1234561, None);
+    let b = GeoCoordinate::new(60.1234562, 30.1234562, Some(15.0));
+    assert_eq!(a.quantize(6), b.quantize(6));
+    assert_ne!(a.quantize(7), b.quantize(7));
+    assert_ne!(a.quantize(6), a.quantize(7));
+
+    let mut keys = std::collections::HashSet::new();
+    keys.insert(a.quantize(6));
+    assert!(!keys.insert(b.quantize(6)));
+    assert_eq!(a.quantize(6).to_coordinate(), GeoCoordinate::new(60.123456, 30.123456, None));
+  }
+
+  #[test]
+  fn test_from_radians()
+  {
+    let t = GeoCoordinate::from_radians(PI / 3.0, PI / 6.0, None);
+    assert_eq!(t, GeoCoordinate::new(60.0, 30.0, None));
+    let (lat, lon) = t.to_radians();
+    assert!((lat - PI / 3.0).abs() < 1e-12);
+    assert!((lon - PI / 6.0).abs() < 1e-12);
+    assert_eq!(GeoCoordinate::from_radians(0.0, 0.0, Some(10.0)).altitude(), Some(10.0));
+  }
+
+  #[test]
+  fn test_vincenty_convergence_failure()
+  {
+    let t = GeoCoordinate::new(0.0, 0.0, None);
+    let near_antipode = GeoCoordinate::new(0.5, 179.7, None);
+    assert!(matches!(t.distance_to_on(&near_antipode, Ellipsoid::Wgs84),
+      Err(PositioningError::ConvergenceFailure { iterations: 200 })));
+    assert!(matches!(t.azimuth_to_ellipsoidal(&near_antipode, Ellipsoid::Wgs84),
+      Err(PositioningError::ConvergenceFailure { .. })));
+    assert!(t.distance_to(&near_antipode).is_ok());
+  }
+
+  #[test]
+  fn test_meridian_convergence()
+  {
+    // zone 36 has its central meridian at 33E
+    assert!(GeoCoordinate::new(60.0, 33.0, None).meridian_convergence().unwrap().abs() < 1e-6);
+    let east = GeoCoordinate::new(60.0, 35.0, None).meridian_convergence().unwrap();
+    assert!(east > 0.0);
+    assert!((east - 1.7323).abs() < 1e-3);
+    assert!(GeoCoordinate::new(60.0, 31.0, None).meridian_convergence().unwrap() < 0.0);
+    assert!(GeoCoordinate::new(-60.0, 35.0, None).meridian_convergence().unwrap() < 0.0);
+    assert!(GeoCoordinate::default().meridian_convergence().is_err());
+  }
+
+  #[test]
+  fn test_antipode()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, Some(100.0));
+    let antipode = t.antipode();
+    assert_eq!(antipode, GeoCoordinate::new(-60.0, -150.0, Some(100.0)));
+    let half_circumference = PI as f32 * Constants::EARTH_MEAN_RADIUS;
+    assert!((t.distance_to(&antipode).unwrap() - half_circumference).abs() < 1.0);
+    assert_eq!(GeoCoordinate::new(0.0, -150.0, None).antipode(), GeoCoordinate::new(0.0, 30.0, None));
+    assert!(t.interpolate(&antipode, 0.5).is_err());
+  }
+
+  #[test]
+  fn test_elevation_angle_to()
+  {
+    let tower = GeoCoordinate::new(60.0, 30.0, Some(50.0));
+    let distant = GeoCoordinate::new(60.0, 31.0, Some(50.0));
+    let angle = tower.elevation_angle_to(&distant).unwrap();
+    assert!(angle < 0.0);
+    assert!((angle - distant.elevation_angle_to(&tower).unwrap()).abs() < 1e-4);
+    assert!((angle + 0.25).abs() < 0.01);
+    let above = GeoCoordinate::new(60.0, 30.0, Some(0.0)).elevation_angle_to(&GeoCoordinate::new(60.0, 30.0, Some(10.0)));
+    assert!((above.unwrap() - 90.0).abs() < 1e-3);
+    assert!(tower.elevation_angle_to(&GeoCoordinate::new(60.0, 31.0, None)).is_err());
+  }
+
+  #[test]
+  fn test_tuple_conversions()
+  {
+    let t: GeoCoordinate = (60.0, 30.0).into();
+    assert_eq!(t, GeoCoordinate::new(60.0, 30.0, None));
+    let elevated: GeoCoordinate = (60.0, 30.0, 100.0).into();
+    assert_eq!(elevated.altitude(), Some(100.0));
+    assert_eq!(elevated.coordinate_type(), GeoCoordinateType::Coordinate3D);
+    let (lat, lon): (f64, f64) = elevated.into();
+    assert_eq!((lat, lon), (60.0, 30.0));
+  }
+
+  #[test]
+  fn test_from_str()
+  {
+    assert_eq!("60.5, 30.25".parse::<GeoCoordinate>().unwrap(), GeoCoordinate::new(60.5, 30.25, None));
+    assert_eq!("-33.9 151.2 12.5".parse::<GeoCoordinate>().unwrap(), GeoCoordinate::new(-33.9, 151.2, Some(12.5)));
+    assert_eq!("60°30'15\"N 30°15'W".parse::<GeoCoordinate>().unwrap(),
+      GeoCoordinate::new(60.0 + 30.0 / 60.0 + 15.0 / 3600.0, -30.25, None));
+    assert_eq!("30°15'E, 12°S, 100".parse::<GeoCoordinate>().unwrap(), GeoCoordinate::new(-12.0, 30.25, Some(100.0)));
+    assert_eq!("59.5N 30.5E".parse::<GeoCoordinate>().unwrap(), GeoCoordinate::new(59.5, 30.5, None));
+    for bad in ["", "60.5", "60.5, abc", "1, 2, 3, 4", "91, 30", "60°75'N 30°E", "60°N 61°N", "60°N", "x°N 30°E"] {
+      assert!(matches!(bad.parse::<GeoCoordinate>(), Err(PositioningError::ParseError(_))), "{}", bad);
+    }
+  }
+
+  #[test]
+  fn test_n_vector()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, Some(5.0));
+    assert_eq!(GeoCoordinate::from_n_vector(t.to_n_vector(), t.altitude), t);
+    let (x, y, z) = GeoCoordinate::new(90.0, 45.0, None).to_n_vector();
+    assert!(x.abs() < 1e-12 && y.abs() < 1e-12 && (z - 1.0).abs() < 1e-12);
+
+    let a = GeoCoordinate::new(89.0, 0.0, None);
+    let b = GeoCoordinate::new(89.0, 180.0, None);
+    let midpoint = a.midpoint(&b).unwrap();
+    assert!((midpoint.latitude - 90.0).abs() < 1e-9);
+    let naive = GeoCoordinate::new((a.latitude + b.latitude) / 2.0, (a.longitude + b.longitude) / 2.0, None);
+    assert!((naive.latitude - 90.0).abs() > 0.5);
+    assert!((a.n_vector_distance_to(&b).unwrap() - 2.0 * 111_194.9).abs() < 1.0);
+    assert!((a.n_vector_distance_to(&midpoint).unwrap() - b.n_vector_distance_to(&midpoint).unwrap()).abs() < 1e-3);
+
+    let far = GeoCoordinate::new(59.0, 31.0, None);
+    assert!((t.n_vector_distance_to(&far).unwrap() - t.distance_to(&far).unwrap()).abs() < 0.1);
+    assert!(t.midpoint(&t.antipode()).is_err());
+  }
+
+  #[test]
+  fn test_to_dms_string()
+  {
+    assert_eq!(GeoCoordinate::new(60.5, 30.25, None).to_dms_string(), "60°30'00\"N 030°15'00\"E");
+    assert_eq!(GeoCoordinate::new(-33.8568, -151.2153, None).to_dms_string(), "33°51'24\"S 151°12'55\"W");
+    assert_eq!(GeoCoordinate::new(-33.8568, -151.2153, None).to_dms_string_with_precision(2),
+      "33°51'24.48\"S 151°12'55.08\"W");
+    assert_eq!(GeoCoordinate::new(5.99999999, 0.0, None).to_dms_string(), "06°00'00\"N 000°00'00\"E");
+    assert_eq!("60°30'15\"N 30°15'W".parse::<GeoCoordinate>().unwrap().to_dms_string(), "60°30'15\"N 030°15'00\"W");
+    assert_eq!(GeoCoordinate::default().to_dms_string(), "invalid");
+  }
+
+  #[test]
+  fn test_wrapped_into()
+  {
+    let rect = GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(0.0, 20.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, 21.0, None).wrapped_into(&rect).unwrap(), GeoCoordinate::new(5.0, 1.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, -1.0, None).wrapped_into(&rect).unwrap(), GeoCoordinate::new(5.0, 19.0, None));
+    assert_eq!(GeoCoordinate::new(15.0, 45.0, None).wrapped_into(&rect).unwrap(), GeoCoordinate::new(10.0, 5.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, 10.0, None).wrapped_into(&rect).unwrap(), GeoCoordinate::new(5.0, 10.0, None));
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, -169.0, None).wrapped_into(&wrapped).unwrap(), GeoCoordinate::new(5.0, 171.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, -175.0, None).wrapped_into(&wrapped).unwrap(), GeoCoordinate::new(5.0, -175.0, None));
+    assert!(GeoCoordinate::default().wrapped_into(&rect).is_err());
+  }
+
+  #[test]
+  fn test_clamp_to_rect()
+  {
+    let rect = GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(0.0, 20.0, None));
+    assert_eq!(GeoCoordinate::new(15.0, 5.0, None).clamp_to_rect(&rect).unwrap(), GeoCoordinate::new(10.0, 5.0, None));
+    let inside = GeoCoordinate::new(5.0, 5.0, Some(10.0));
+    assert_eq!(inside.clamp_to_rect(&rect).unwrap(), inside);
+    assert_eq!(GeoCoordinate::new(-5.0, 25.0, None).clamp_to_rect(&rect).unwrap(), GeoCoordinate::new(0.0, 20.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, -170.0, None).clamp_to_rect(&rect).unwrap(), GeoCoordinate::new(5.0, 0.0, None));
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, -160.0, None).clamp_to_rect(&wrapped).unwrap(), GeoCoordinate::new(5.0, -170.0, None));
+    assert_eq!(GeoCoordinate::new(5.0, 179.0, None).clamp_to_rect(&wrapped).unwrap(), GeoCoordinate::new(5.0, 179.0, None));
+    assert!(GeoCoordinate::default().clamp_to_rect(&rect).is_err());
+    assert!(inside.clamp_to_rect(&GeoRectangle::default()).is_err());
+  }
+
+  #[test]
+  fn test_distance_gradient_to()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let h = 1e-4;
+    for other in [GeoCoordinate::new(59.0, 31.0, None), GeoCoordinate::new(60.5, 28.0, None), GeoCoordinate::new(-10.0, 100.0, None)] {
+      let (d_lat, d_lon) = t.distance_gradient_to(&other).unwrap();
+      let shifted = |lat: f64, lon: f64| t.distance_to(&GeoCoordinate::from_radians(
+        other.latitude.to_radians() + lat, other.longitude.to_radians() + lon, None)).unwrap() as f64;
+      let numeric_lat = (shifted(h, 0.0) - shifted(-h, 0.0)) / (2.0 * h);
+      let numeric_lon = (shifted(0.0, h) - shifted(0.0, -h)) / (2.0 * h);
+      assert!((d_lat - numeric_lat).abs() < 1e-3 * Constants::EARTH_MEAN_RADIUS as f64, "{} {}", d_lat, numeric_lat);
+      assert!((d_lon - numeric_lon).abs() < 1e-3 * Constants::EARTH_MEAN_RADIUS as f64, "{} {}", d_lon, numeric_lon);
+    }
+    assert!(t.distance_gradient_to(&t).is_err());
+  }
+
+  #[test]
+  fn test_round_to_precision()
+  {
+    let t = GeoCoordinate::new(60.123456789, 30.987654321, Some(12.3456));
+    let rounded = t.round_to_precision(3);
+    assert_eq!(rounded.latitude, 60.123);
+    assert_eq!(rounded.longitude, 30.988);
+    assert_eq!(rounded.altitude, Some(12.35));
+    assert_eq!(t.round_to_precision(0), GeoCoordinate::new(60.0, 31.0, Some(12.35)));
+    assert_eq!(GeoCoordinate::new(-0.55, 0.0, None).round_to_precision(1).latitude, -0.6);
+  }
+
+  #[test]
+  fn test_dead_reckon()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let moved = t.dead_reckon(10.0, 0.0, Duration::from_secs(60)).unwrap();
+    assert!((t.distance_to(&moved).unwrap() - 600.0).abs() < 0.01);
+    assert!(moved.latitude > t.latitude);
+    assert!((moved.longitude - t.longitude).abs() < 1e-9);
+    assert_eq!(t.dead_reckon(10.0, 90.0, Duration::ZERO).unwrap(), t);
+    assert!(t.dead_reckon(f32::NAN, 0.0, Duration::from_secs(1)).is_err());
+  }
+
+  #[test]
+  fn test_approx_eq()
+  {
+    let a = GeoCoordinate::new(60.0, 30.0, Some(10.0));
+    let b = GeoCoordinate::new(60.00001, 30.00001, Some(10.5));
+    assert!(a.approx_eq(&b, 1e-4));
+    assert!(!a.approx_eq(&b, 1e-9));
+    assert!(a.approx_eq_3d(&b, 1e-4, 1.0));
+    assert!(!a.approx_eq_3d(&b, 1e-4, 0.1));
+    assert!(!a.approx_eq_3d(&b.without_altitude(), 1e-4, 1.0));
+    assert_ne!(a, b);
+    assert_eq!(a, GeoCoordinate::new(60.0000001, 30.0, None));
+    assert_ne!(a, GeoCoordinate::new(60.0000001, 30.0, Some(11.0)));
+    let flat = GeoCoordinate::new(60.0, 30.0, None);
+    assert_eq!(a == flat, flat == a);
+    assert_eq!(flat, a);
+  }
+
+  #[test]
+  fn test_project_onto_rhumb_segment()
+  {
+    let a = GeoCoordinate::new(70.0, 0.0, None);
+    let b = GeoCoordinate::new(70.0, 20.0, None);
+    let t = GeoCoordinate::new(70.5, 10.0, None);
+    let rhumb = t.project_onto_rhumb_segment(&a, &b).unwrap();
+    assert!(rhumb.approx_eq(&GeoCoordinate::new(70.0, 10.0, None), 1e-6));
+    let great_circle = a.interpolate(&b, t.great_circle_fraction(&a, &b).unwrap()).unwrap();
+    assert!(great_circle.latitude - rhumb.latitude > 0.1);
+    assert!(t.distance_to(&great_circle).unwrap() < t.distance_to(&rhumb).unwrap());
+
+    assert!(GeoCoordinate::new(69.0, -5.0, None).project_onto_rhumb_segment(&a, &b).unwrap().approx_eq(&a, 1e-6));
+    let (start, end) = (GeoCoordinate::new(59.0, 29.0, None), GeoCoordinate::new(61.0, 31.0, None));
+    let on_line = start.rhumb_destination(start.rhumb_distance_to(&end).unwrap() / 3.0, start.rhumb_azimuth_to(&end).unwrap())
+      .unwrap();
+    assert!(on_line.project_onto_rhumb_segment(&start, &end).unwrap().approx_eq(&on_line, 1e-5));
+    assert!(t.project_onto_rhumb_segment(&a, &GeoCoordinate::new(90.0, 0.0, None)).is_err());
+  }
+
+  #[test]
+  fn test_antimeridian_equality()
+  {
+    assert_eq!(GeoCoordinate::new(0.0, 180.0, None), GeoCoordinate::new(0.0, -180.0, None));
+    assert_eq!(GeoCoordinate::new(10.0, -180.0, Some(5.0)), GeoCoordinate::new(10.0, 180.0, Some(5.0)));
+    assert_eq!(GeoCoordinate::new(0.0, 179.99999999, None), GeoCoordinate::new(0.0, -180.0, None));
+    assert_ne!(GeoCoordinate::new(1.0, 180.0, None), GeoCoordinate::new(0.0, -180.0, None));
+    assert_ne!(GeoCoordinate::new(0.0, 179.0, None), GeoCoordinate::new(0.0, -180.0, None));
+    assert!(GeoCoordinate::new(0.0, 180.0, None).approx_eq(&GeoCoordinate::new(0.0, -179.5, None), 0.6));
+  }
+
+  #[test]
+  fn test_tangent_bearing_at()
+  {
+    let start = GeoCoordinate::new(51.5, -0.12, None);
+    let end = GeoCoordinate::new(40.7, -74.0, None);
+    let initial = start.azimuth_to(&end).unwrap();
+    let last = (end.azimuth_to(&start).unwrap() + 180.0) % 360.0;
+    assert!((start.tangent_bearing_at(&end, 0.0).unwrap() - initial).abs() < 1e-3);
+    assert!((start.tangent_bearing_at(&end, 1.0).unwrap() - last).abs() < 1e-3);
+    let middle = start.tangent_bearing_at(&end, 0.5).unwrap();
+    assert!(middle > initial.min(last) && middle < initial.max(last));
+    let on_equator = GeoCoordinate::new(0.0, 0.0, None).tangent_bearing_at(&GeoCoordinate::new(0.0, 50.0, None), 0.7).unwrap();
+    assert!((on_equator - 90.0).abs() < 1e-3);
+    assert!(start.tangent_bearing_at(&start, 0.5).is_err());
+    assert!(start.tangent_bearing_at(&end, 1.5).is_err());
+  }
+
+  #[test]
+  fn test_is_near()
+  {
+    let t = GeoCoordinate::new(60.0, 30.0, None);
+    let other = GeoCoordinate::new(59.0, 30.0, None);
+    assert!(t.is_near(&other, 111200.0).unwrap());
+    assert!(!t.is_near(&other, 111190.0).unwrap());
+    assert!(t.is_near(&t, 0.0).unwrap());
+    assert!(t.is_near(&GeoCoordinate::default(), 1.0).is_err());
+  }
+
+  #[test]
+  fn test_bytes_round_trip()
+  {
+    let flat = GeoCoordinate::new(60.123456789, -30.987654321, None);
+    let bytes = flat.to_bytes();
+    assert_eq!(bytes.len(), 20);
+    assert_eq!(&bytes[0..8], &60.123456789f64.to_le_bytes());
+    let decoded = GeoCoordinate::from_bytes(&bytes);
+    assert_eq!(decoded.latitude(), flat.latitude());
+    assert_eq!(decoded.longitude(), flat.longitude());
+    assert_eq!(decoded.altitude(), None);
+
+    let raised = GeoCoordinate::new(-45.5, 179.25, Some(1234.5));
+    let decoded = GeoCoordinate::from_bytes(&raised.to_bytes());
+    assert_eq!(decoded.latitude(), raised.latitude());
+    assert_eq!(decoded.longitude(), raised.longitude());
+    assert_eq!(decoded.altitude(), Some(1234.5));
+  }
 }
\ No newline at end of file
@@ -0,0 +1,39 @@
+use crate::positioning::constants as Constants;
+
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Ellipsoid
+{
+  #[default]
+  Sphere,
+  Wgs84,
+  Grs80,
+  Custom { a: f64, f: f64 }
+}
+
+impl Ellipsoid
+{
+  pub fn semi_major_axis(&self) -> f64
+  {
+    match self {
+      Ellipsoid::Sphere => Constants::EARTH_MEAN_RADIUS as f64,
+      Ellipsoid::Wgs84 => Constants::WGS84_SEMI_MAJOR_AXIS,
+      Ellipsoid::Grs80 => Constants::GRS80_SEMI_MAJOR_AXIS,
+      Ellipsoid::Custom { a, .. } => *a
+    }
+  }
+
+  pub fn flattening(&self) -> f64
+  {
+    match self {
+      Ellipsoid::Sphere => 0.0,
+      Ellipsoid::Wgs84 => Constants::WGS84_FLATTENING,
+      Ellipsoid::Grs80 => Constants::GRS80_FLATTENING,
+      Ellipsoid::Custom { f, .. } => *f
+    }
+  }
+
+  pub fn semi_minor_axis(&self) -> f64
+  {
+    self.semi_major_axis() * (1.0 - self.flattening())
+  }
+}
\ No newline at end of file
@@ -1,16 +1,38 @@
 use thiserror::Error;
 use crate::positioning::coordinate::GeoCoordinate;
 use crate::positioning::georectangle::GeoRectangle;
+use crate::positioning::geoshape::GeoCircle;
 
-#[derive(Debug, Error)]
+#[derive(Debug, Error, PartialEq)]
 pub enum PositioningError
 {
   #[error("Operation on invalid coordinate: {0}")]
   InvalidCoordinate(GeoCoordinate),
 
+  #[error("Operation on invalid coordinate at index {0}: {1}")]
+  InvalidCoordinateAt(usize, GeoCoordinate),
+
   #[error("Operation on invalid georectangle: {0}")]
   InvalidGeorectangle(GeoRectangle),
 
+  #[error("Operation on invalid geocircle: {0}")]
+  InvalidGeocircle(GeoCircle),
+
   #[error("Index out of bounds: {0} out of {1}")]
-  IndexOutOfBounds(usize, usize)
+  IndexOutOfBounds(usize, usize),
+
+  #[error("Invalid builder configuration: {0}")]
+  InvalidBuilderConfiguration(&'static str),
+
+  #[error("Invalid argument: {0}")]
+  InvalidArgument(&'static str),
+
+  #[error("Parse error: {0}")]
+  ParseError(String),
+
+  #[error("Not enough points: needed at least {needed}, got {got}")]
+  NotEnoughPoints { needed: usize, got: usize },
+
+  #[error("Iterative solver did not converge after {iterations} iterations")]
+  ConvergenceFailure { iterations: usize }
 }
\ No newline at end of file
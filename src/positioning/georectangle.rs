@@ -1,7 +1,9 @@
 use std::fmt::Display;
+use crate::positioning::constants as Constants;
 use crate::positioning::errors::PositioningError;
-use crate::positioning::{CardinalDirection, GeoCoordinate};
-use crate::positioning::utility::CoordinateField;
+use crate::positioning::mercator;
+use crate::positioning::{CardinalDirection, GeoCircle, GeoCoordinate, GeoPath};
+use crate::positioning::utility::{normalize_longitude, CoordinateField};
 use crate::positioning::utility::CoordinateFieldType::Longitude;
 
 #[derive(Debug, Clone, PartialEq)]
@@ -11,6 +13,16 @@ pub struct GeoRectangle
   br: GeoCoordinate
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct GeoRectangleBuilder
+{
+  tl: Option<GeoCoordinate>,
+  br: Option<GeoCoordinate>,
+  center: Option<GeoCoordinate>,
+  width_degrees: Option<f64>,
+  height_degrees: Option<f64>
+}
+
 impl Default for GeoRectangle
 {
   fn default() -> Self
@@ -29,6 +41,12 @@ impl Display for GeoRectangle
 
 impl GeoRectangle
 {
+  pub const OUTCODE_INSIDE: u8 = 0;
+  pub const OUTCODE_LEFT: u8 = 1;
+  pub const OUTCODE_RIGHT: u8 = 2;
+  pub const OUTCODE_BOTTOM: u8 = 4;
+  pub const OUTCODE_TOP: u8 = 8;
+
   pub fn new(tl: GeoCoordinate, br: GeoCoordinate) -> Self
   {
     Self { tl, br }
@@ -56,10 +74,75 @@ impl GeoRectangle
     Ok(rect)
   }
 
+  /// Alternative to `from_center_meters` that offsets the edges directly by the local meters-per-degree at
+  /// the center latitude instead of chaining two geodesic steps per corner. The box is symmetric about the
+  /// center and its height is exact; the width is exact along the center parallel and shrinks slightly
+  /// towards the pole-side edge, so prefer this one for small boxes and the geodesic one for large ones.
+  pub fn from_center_meters_exact(center: GeoCoordinate, width_meters: f32, height_meters: f32)
+                                  -> Result<Self, PositioningError>
+  {
+    if !center.valid() { return Err(PositioningError::InvalidCoordinate(center)) }
+    if width_meters < 0.0 || height_meters < 0.0 {
+      return Err(PositioningError::InvalidArgument("box dimensions must not be negative"))
+    }
+    let meters_per_degree = (Constants::EARTH_MEAN_RADIUS as f64).to_radians();
+    let half_height = height_meters as f64 / 2.0 / meters_per_degree;
+    let (top, bottom) = (center.latitude + half_height, center.latitude - half_height);
+    if top > 90.0 || bottom < -90.0 { return Err(PositioningError::InvalidArgument("box extends past a pole")) }
+    let span = width_meters as f64 / (meters_per_degree * center.latitude.to_radians().cos());
+    Ok(Self::from_longitude_span(top, bottom, center.longitude - span / 2.0, span))
+  }
+
+  pub fn builder() -> GeoRectangleBuilder
+  {
+    GeoRectangleBuilder::default()
+  }
+
+  /// Smallest box containing every valid coordinate. The longitude span is the complement of the largest
+  /// gap between neighbouring longitudes, so points on both sides of the antimeridian give a wrapped box.
   pub fn from_list(coordinates: &Vec<GeoCoordinate>) -> Self
   {
-    //if coordinates.len() < 2 { return GeoRectangle::default() }
-    todo!("Implement GeoRectangle::from_list")
+    if coordinates.len() < 2 { return GeoRectangle::default() }
+    let (mut top, mut bottom) = (f64::MIN, f64::MAX);
+    let mut longitudes = Vec::with_capacity(coordinates.len());
+    for c in coordinates.iter().filter(|c| c.valid()) {
+      top = top.max(c.latitude);
+      bottom = bottom.min(c.latitude);
+      longitudes.push(c.longitude);
+    }
+    if top < bottom { return GeoRectangle::default() }
+    longitudes.sort_by(f64::total_cmp);
+    let (first, last) = (longitudes[0], longitudes[longitudes.len() - 1]);
+    let (mut left, mut gap) = (first, first + 360.0 - last);
+    for pair in longitudes.windows(2) {
+      if pair[1] - pair[0] > gap { (left, gap) = (pair[1], pair[1] - pair[0]) }
+    }
+    Self::from_longitude_span(top, bottom, left, 360.0 - gap)
+  }
+
+  /// Parses a `"west,south,east,north"` extent. `west > east` describes a box crossing the antimeridian.
+  pub fn from_csv_bounds(bounds: &str) -> Result<Self, PositioningError>
+  {
+    let values = bounds
+      .split(',')
+      .map(|v| v.trim().parse::<f64>().map_err(|e| PositioningError::ParseError(format!("{}: {}", v.trim(), e))))
+      .collect::<Result<Vec<f64>, PositioningError>>()?;
+    let [west, south, east, north] = values[..] else {
+      return Err(PositioningError::ParseError(format!("expected 4 comma-separated values, got {}", values.len())))
+    };
+    if !(-180.0..=180.0).contains(&west) || !(-180.0..=180.0).contains(&east) {
+      return Err(PositioningError::ParseError(format!("longitude out of range in {}", bounds)))
+    }
+    if !(-90.0..=90.0).contains(&south) || !(-90.0..=90.0).contains(&north) {
+      return Err(PositioningError::ParseError(format!("latitude out of range in {}", bounds)))
+    }
+    if south > north { return Err(PositioningError::ParseError(format!("south is above north in {}", bounds))) }
+    Ok(Self::new(GeoCoordinate::new(north, west, None), GeoCoordinate::new(south, east, None)))
+  }
+
+  pub fn to_csv_bounds(&self) -> String
+  {
+    format!("{},{},{},{}", self.tl.longitude, self.br.latitude, self.br.longitude, self.tl.latitude)
   }
 
   pub fn bottom_right(&self) -> GeoCoordinate { self.br }
@@ -73,35 +156,73 @@ impl GeoRectangle
     GeoCoordinate::new(self.tl.latitude, self.br.longitude, None)
   }
 
+  pub fn corners(&self) -> [GeoCoordinate; 4]
+  {
+    [self.top_left(), self.top_right(), self.bottom_right(), self.bottom_left()]
+  }
+
+  pub fn to_path(&self) -> GeoPath
+  {
+    let mut path = GeoPath::new(&self.corners().to_vec());
+    path.close_loop();
+    path
+  }
+
   pub fn center(&self) -> GeoCoordinate
   {
     if !self.valid() { return GeoCoordinate::default() }
+    let br_longitude = if self.tl.longitude > self.br.longitude { self.br.longitude + 360.0 } else { self.br.longitude };
+    let longitude = (self.tl.longitude + br_longitude) / 2.0;
     GeoCoordinate::new(
       (self.tl.latitude + self.br.latitude) / 2.0,
-      if self.tl.longitude > self.br.longitude {
-        (self.br.longitude + self.tl.longitude) / 2.0 - 180.0
-      } else { (self.br.longitude + self.tl.longitude) / 2.0 }.wrap(Longitude),
+      normalize_longitude(longitude),
       None
     )
   }
 
+  /// Same as `contains_2d`: altitude is ignored.
   pub fn contains(&self, coordinate: &GeoCoordinate) -> Result<bool, PositioningError>
+  {
+    self.contains_2d(coordinate)
+  }
+
+  /// Tests latitude and longitude only. The altitude of the coordinate and of the corners is ignored.
+  pub fn contains_2d(&self, coordinate: &GeoCoordinate) -> Result<bool, PositioningError>
+  {
+    self.contains_with(coordinate, true)
+  }
+
+  /// If both corners carry an altitude, the rectangle is treated as a box spanning the altitudes
+  /// between them and the coordinate must carry an altitude within that span. Otherwise it behaves
+  /// like `contains_2d`.
+  pub fn contains_3d(&self, coordinate: &GeoCoordinate) -> Result<bool, PositioningError>
+  {
+    if !self.contains_2d(coordinate)? { return Ok(false) }
+    let (Some(top), Some(bottom)) = (self.tl.altitude, self.br.altitude) else { return Ok(true) };
+    Ok(coordinate.altitude.is_some_and(|alt| alt >= top.min(bottom) && alt <= top.max(bottom)))
+  }
+
+  /// With `inclusive = false` the top latitude and right longitude edges are excluded, so that
+  /// adjacent tiles do not both claim a point on their shared border.
+  pub fn contains_with(&self, coordinate: &GeoCoordinate, inclusive: bool) -> Result<bool, PositioningError>
   {
     if !self.valid() { return Err(PositioningError::InvalidCoordinate(self.tl.clone())) }
     if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(coordinate.clone())) }
 
-    if coordinate.latitude > self.tl.latitude || coordinate.latitude < self.br.latitude {
+    let past_top = if inclusive { coordinate.latitude > self.tl.latitude } else { coordinate.latitude >= self.tl.latitude };
+    if past_top || coordinate.latitude < self.br.latitude {
       return Ok(false)
     }
     if coordinate.latitude == 90.0 && self.tl.latitude == 90.0 { return Ok(true) }
     if coordinate.latitude == -90.0 && self.br.latitude == -90.0 { return Ok(true) }
+    let past_right = if inclusive { coordinate.longitude > self.br.longitude } else { coordinate.longitude >= self.br.longitude };
     if self.tl.longitude <= self.br.longitude {
-      if coordinate.longitude < self.tl.longitude || coordinate.longitude > self.br.longitude {
+      if coordinate.longitude < self.tl.longitude || past_right {
         return Ok(false)
       }
     }
     else {
-      if coordinate.longitude < self.tl.longitude && coordinate.longitude > self.br.longitude {
+      if coordinate.longitude < self.tl.longitude && past_right {
         return Ok(false)
       }
     }
@@ -115,6 +236,22 @@ impl GeoRectangle
     Ok(ret)
   }
 
+  pub fn contains_circle(&self, circle: &GeoCircle) -> Result<bool, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    if !circle.valid() { return Err(PositioningError::InvalidGeocircle(circle.clone())) }
+    let center = circle.center();
+    let angular_radius = (circle.radius_meters() / Constants::EARTH_MEAN_RADIUS) as f64;
+    let (north, south) = (center.latitude + angular_radius.to_degrees(), center.latitude - angular_radius.to_degrees());
+    if north > self.tl.latitude || south < self.br.latitude { return Ok(false) }
+    if self.width() >= 360.0 { return Ok(true) }
+    let ratio = angular_radius.sin() / center.latitude.to_radians().cos();
+    if ratio >= 1.0 { return Ok(false) }
+    let half_span = ratio.asin().to_degrees();
+    let offset = (center.longitude - self.tl.longitude).rem_euclid(360.0);
+    Ok(offset - half_span >= 0.0 && offset + half_span <= self.width())
+  }
+
   pub fn width(&self) -> f64
   {
     if !self.valid() { return 0.0 }
@@ -148,42 +285,353 @@ impl GeoRectangle
     Ok(h)
   }
 
+  /// Closest point on the rectangle edge. Outside points are clamped onto the box in the lat/lon plane;
+  /// inside points are moved to whichever edge is nearest in meters.
+  pub fn nearest_boundary_point(&self, coordinate: &GeoCoordinate) -> Result<GeoCoordinate, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+    let (left, right) = (self.tl.longitude, self.tl.longitude + self.width());
+    let lon = self.frame_longitude(coordinate.longitude);
+    let lat = coordinate.latitude;
+    if self.frame_outcode(lat, lon) != Self::OUTCODE_INSIDE {
+      return Ok(GeoCoordinate::new(
+        lat.clamp(self.br.latitude, self.tl.latitude),
+        normalize_longitude(lon.clamp(left, right)),
+        None
+      ))
+    }
+    let candidates = [
+      GeoCoordinate::new(self.tl.latitude, normalize_longitude(lon), None),
+      GeoCoordinate::new(self.br.latitude, normalize_longitude(lon), None),
+      GeoCoordinate::new(lat, normalize_longitude(left), None),
+      GeoCoordinate::new(lat, normalize_longitude(right), None)
+    ];
+    let distances = coordinate.distances_to_many(&candidates)?;
+    let nearest = (0..candidates.len())
+      .min_by(|&a, &b| distances[a].total_cmp(&distances[b]))
+      .unwrap();
+    Ok(candidates[nearest])
+  }
+
+  pub fn contained_fraction(&self, path: &GeoPath) -> Result<f32, PositioningError>
+  {
+    let parts = self.clip_path(path)?;
+    let total = path.total_length()?;
+    if total == 0.0 { return Ok(if parts.is_empty() { 0.0 } else { 1.0 }) }
+    let inside = parts
+      .iter()
+      .map(|part| part.total_length())
+      .sum::<Result<f32, PositioningError>>()?;
+    Ok((inside / total).min(1.0))
+  }
+
+  /// Equirectangular mapping of the rectangle onto a `pixel_width` x `pixel_height` canvas,
+  /// with the top-left corner at (0, 0) and y growing southwards.
+  pub fn map_to_pixel(&self, coordinate: &GeoCoordinate, pixel_width: u32, pixel_height: u32)
+    -> Result<(f64, f64), PositioningError>
+  {
+    self.check_canvas(pixel_width, pixel_height)?;
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+    Ok((
+      (self.frame_longitude(coordinate.longitude) - self.tl.longitude) / self.width() * pixel_width as f64,
+      (self.tl.latitude - coordinate.latitude) / self.height() * pixel_height as f64
+    ))
+  }
+
+  pub fn pixel_to_map(&self, x: f64, y: f64, pixel_width: u32, pixel_height: u32) -> Result<GeoCoordinate, PositioningError>
+  {
+    self.check_canvas(pixel_width, pixel_height)?;
+    let longitude = self.tl.longitude + x / pixel_width as f64 * self.width();
+    Ok(GeoCoordinate::new(
+      self.tl.latitude - y / pixel_height as f64 * self.height(),
+      normalize_longitude(longitude),
+      None
+    ))
+  }
+
+  pub fn mercator_pixel_size(&self, zoom: u8) -> Result<(f64, f64), PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let size = mercator::world_size(zoom);
+    Ok((
+      self.width() / 360.0 * size,
+      (mercator::normalized_y(self.br.latitude) - mercator::normalized_y(self.tl.latitude)) * size
+    ))
+  }
+
+  pub fn tiles_capped(&self, max_tiles: usize) -> Result<(u8, Vec<(u32, u32)>), PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    if max_tiles == 0 { return Ok((0, Vec::new())) }
+    let mut zoom = 0;
+    for z in 1..=mercator::MAX_ZOOM {
+      if self.tile_columns(z).len() * self.tile_rows(z).count() > max_tiles { break }
+      zoom = z;
+    }
+    let rows = self.tile_rows(zoom);
+    let tiles = self
+      .tile_columns(zoom)
+      .into_iter()
+      .flat_map(|x| rows.clone().map(move |y| (x, y)))
+      .collect();
+    Ok((zoom, tiles))
+  }
+
+  pub fn perimeter_meters(&self) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let corners = self.corners();
+    let mut perimeter = 0.0;
+    for i in 0..corners.len() {
+      perimeter += corners[i].distance_to(&corners[(i + 1) % corners.len()])?;
+    }
+    Ok(perimeter)
+  }
+
+  pub fn subdivide(&self, rows: usize, cols: usize) -> Result<Vec<GeoRectangle>, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    if rows == 0 || cols == 0 { return Err(PositioningError::InvalidArgument("rows and cols must be positive")) }
+    let cell_width = self.width() / cols as f64;
+    let cell_height = self.height() / rows as f64;
+    let mut cells = Vec::with_capacity(rows * cols);
+    for row in 0..rows {
+      let top = self.tl.latitude - row as f64 * cell_height;
+      for col in 0..cols {
+        let left = self.tl.longitude + col as f64 * cell_width;
+        let right = left + cell_width;
+        cells.push(GeoRectangle::new(
+          GeoCoordinate::new(top, normalize_longitude(left), None),
+          GeoCoordinate::new(top - cell_height, normalize_longitude(right), None)
+        ));
+      }
+    }
+    Ok(cells)
+  }
+
+  pub fn split_at_latitude(&self, latitude: f64) -> Option<(GeoRectangle, GeoRectangle)>
+  {
+    if !self.valid() || latitude >= self.tl.latitude || latitude <= self.br.latitude { return None }
+    Some((
+      GeoRectangle::new(self.tl, GeoCoordinate::new(latitude, self.br.longitude, None)),
+      GeoRectangle::new(GeoCoordinate::new(latitude, self.tl.longitude, None), self.br)
+    ))
+  }
+
+  pub fn split_at_longitude(&self, longitude: f64) -> Option<(GeoRectangle, GeoRectangle)>
+  {
+    if !self.valid() || !longitude.valid(Longitude) { return None }
+    let frame = self.frame_longitude(longitude);
+    if frame <= self.tl.longitude || frame >= self.tl.longitude + self.width() { return None }
+    let boundary = normalize_longitude(frame);
+    Some((
+      GeoRectangle::new(self.tl, GeoCoordinate::new(self.br.latitude, boundary, None)),
+      GeoRectangle::new(GeoCoordinate::new(self.tl.latitude, boundary, None), self.br)
+    ))
+  }
+
+  pub fn split_at_antimeridian(&self) -> Vec<GeoRectangle>
+  {
+    if !self.valid() { return Vec::new() }
+    match self.split_at_longitude(180.0) {
+      Some((west, east)) => vec![west, east],
+      None => vec![self.clone()]
+    }
+  }
+
+  pub fn expanded_to_square(&self) -> Result<GeoRectangle, PositioningError>
+  {
+    const MAX_ITERATIONS: usize = 32;
+
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let radius = Constants::EARTH_MEAN_RADIUS as f64;
+    let center_lon = self.tl.longitude + self.width() / 2.0;
+    let center_lat = (self.tl.latitude + self.br.latitude) / 2.0;
+    let (mut width, mut top, mut bottom) = (self.width(), self.tl.latitude, self.br.latitude);
+    let build = |width: f64, top: f64, bottom: f64| {
+      if width >= 360.0 {
+        return GeoRectangle::new(GeoCoordinate::new(top, -180.0, None), GeoCoordinate::new(bottom, 180.0, None))
+      }
+      GeoRectangle::new(
+        GeoCoordinate::new(top, normalize_longitude(center_lon - width / 2.0), None),
+        GeoCoordinate::new(bottom, normalize_longitude(center_lon + width / 2.0), None)
+      )
+    };
+    for _ in 0..MAX_ITERATIONS {
+      let rect = build(width, top, bottom);
+      let (w, h) = (rect.width_meters()? as f64, rect.height_meters()? as f64);
+      if (w - h).abs() <= 1e-4 * w.max(h) { break }
+      if w < h {
+        if width >= 360.0 { break }
+        let ratio = (h / (2.0 * radius)).sin() / top.to_radians().cos();
+        width = if ratio >= 1.0 { 360.0 } else { 2.0 * ratio.asin().to_degrees() };
+      } else {
+        if top >= 90.0 && bottom <= -90.0 { break }
+        let half = (w / radius).to_degrees() / 2.0;
+        top = (center_lat + half).min(90.0);
+        bottom = (center_lat - half).max(-90.0);
+      }
+    }
+    Ok(build(width, top, bottom))
+  }
+
+  pub fn grown_to_min(&self, min_width_meters: f32, min_height_meters: f32) -> Result<GeoRectangle, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let radius = Constants::EARTH_MEAN_RADIUS as f64;
+    let (mut top, mut bottom) = (self.tl.latitude, self.br.latitude);
+    if self.height_meters()? < min_height_meters {
+      let center = (top + bottom) / 2.0;
+      let half = (min_height_meters as f64 / radius).to_degrees() / 2.0;
+      (top, bottom) = ((center + half).min(90.0), (center - half).max(-90.0));
+    }
+    let mut width = self.width();
+    let top_width = Self::from_longitude_span(top, bottom, self.tl.longitude, width).width_meters()?;
+    if top_width < min_width_meters {
+      let ratio = (min_width_meters as f64 / (2.0 * radius)).sin() / top.to_radians().cos();
+      width = if ratio >= 1.0 || !ratio.is_finite() { 360.0 } else { 2.0 * ratio.asin().to_degrees() };
+    }
+    let center = self.tl.longitude + self.width() / 2.0;
+    Ok(Self::from_longitude_span(top, bottom, center - width / 2.0, width))
+  }
+
+  pub fn expanded_by_meters(&self, meters: f32) -> Result<GeoRectangle, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let (distance, outward) = (meters.abs(), meters >= 0.0);
+    let (mid_lat, mid_lon) = ((self.tl.latitude + self.br.latitude) / 2.0, normalize_longitude(self.tl.longitude + self.width() / 2.0));
+    let shift = |from: GeoCoordinate, azimuth: f32| {
+      from.at_distance_and_azimuth(distance, if outward { azimuth } else { (azimuth + 180.0) % 360.0 })
+    };
+
+    let delta_lat = (distance as f64 / Constants::EARTH_MEAN_RADIUS as f64).to_degrees();
+    let sign = if outward { 1.0 } else { -1.0 };
+    let top = if self.tl.latitude + sign * delta_lat >= 90.0 { 90.0 }
+      else { shift(GeoCoordinate::new(self.tl.latitude, mid_lon, None), 0.0)?.latitude };
+    let bottom = if self.br.latitude - sign * delta_lat <= -90.0 { -90.0 }
+      else { shift(GeoCoordinate::new(self.br.latitude, mid_lon, None), 180.0)?.latitude };
+
+    let west = shift(GeoCoordinate::new(mid_lat, self.tl.longitude, None), 270.0)?;
+    let east = shift(GeoCoordinate::new(mid_lat, self.br.longitude, None), 90.0)?;
+    let west_shift = normalize_longitude(west.longitude - self.tl.longitude);
+    let width = self.width() - west_shift + normalize_longitude(east.longitude - self.br.longitude);
+
+    if top < bottom || width < 0.0 {
+      let center = GeoCoordinate::new(mid_lat, mid_lon, None);
+      return Ok(GeoRectangle::new(center, center))
+    }
+    if width >= 360.0 {
+      return Ok(GeoRectangle::new(GeoCoordinate::new(top, -180.0, None), GeoCoordinate::new(bottom, 180.0, None)))
+    }
+    let left = self.tl.longitude + west_shift;
+    Ok(GeoRectangle::new(
+      GeoCoordinate::new(top, normalize_longitude(left), None),
+      GeoCoordinate::new(bottom, normalize_longitude(left + width), None)
+    ))
+  }
+
   pub fn intersects(&self, other: &GeoRectangle) -> bool
   {
     if self.tl.latitude < other.br.latitude || self.br.latitude > other.tl.latitude { return false }
     if self.tl.latitude == 90.0 && self.tl.latitude == other.tl.latitude { return true }
     if self.br.latitude == -90.0 && self.br.latitude == other.br.latitude { return true }
-    if self.tl.longitude < self.br.longitude
-    {
-      if other.tl.longitude < other.br.longitude {
-        if self.tl.longitude > other.br.longitude || self.br.longitude < other.tl.longitude {
-          return false
+    let (start, end) = (self.tl.longitude, self.tl.longitude + self.width());
+    let other_start = start + (other.tl.longitude - start).rem_euclid(360.0);
+    [other_start - 360.0, other_start].iter().any(|&s| s <= end && s + other.width() >= start)
+  }
+
+  /// Cohen-Sutherland region code of the coordinate relative to the rectangle edges.
+  /// Left/right are decided by the shorter way around the globe from the rectangle center.
+  /// Invalid inputs yield all four bits set.
+  pub fn outcode(&self, coordinate: &GeoCoordinate) -> u8
+  {
+    if !self.valid() || !coordinate.valid() {
+      return Self::OUTCODE_LEFT | Self::OUTCODE_RIGHT | Self::OUTCODE_BOTTOM | Self::OUTCODE_TOP
+    }
+    self.frame_outcode(coordinate.latitude, self.frame_longitude(coordinate.longitude))
+  }
+
+  pub fn clip_path(&self, path: &GeoPath) -> Result<Vec<GeoPath>, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let mut parts = Vec::new();
+    let mut current: Vec<GeoCoordinate> = Vec::new();
+    for (i, coordinate) in path.path().iter().enumerate() {
+      if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+      if i == 0 {
+        if path.size() == 1 && self.contains(coordinate)? { current.push(*coordinate) }
+        continue
+      }
+      match self.clip_segment(&path.path()[i - 1], coordinate) {
+        None => {
+          if current.len() > 1 { parts.push(GeoPath::new(&current)) }
+          current.clear();
         }
-      } else {
-        if self.tl.longitude > other.br.longitude && self.br.longitude < other.tl.longitude {
-          return false
+        Some((start, end, _)) if current.is_empty() && start == end => continue,
+        Some((start, end, end_clipped)) => {
+          if current.is_empty() { current.push(start) }
+          if current.last() != Some(&end) { current.push(end) }
+          if end_clipped {
+            if current.len() > 1 { parts.push(GeoPath::new(&current)) }
+            current.clear();
+          }
         }
       }
     }
-    else
-    {
-      if other.tl.longitude < other.br.longitude {
-        if other.tl.longitude > self.br.longitude && other.br.longitude < self.tl.longitude {
-          return false
-        }
-      } else { return true }
-    }
-    true
+    if !current.is_empty() { parts.push(GeoPath::new(&current)) }
+    Ok(parts)
   }
 
   pub fn union(&self, other: &GeoRectangle) -> Self
   {
-    todo!("Implement GeoRectangle::union")
+    if !self.valid() { return other.clone() }
+    if !other.valid() { return self.clone() }
+    let (start, width) = (self.tl.longitude, self.width());
+    let other_start = start + (other.tl.longitude - start).rem_euclid(360.0);
+    let east_extent = (other_start + other.width()).max(start + width) - start;
+    let west_extent = (start + 360.0 + width).max(other_start + other.width()) - other_start;
+    let (left, span) = if east_extent <= west_extent { (start, east_extent) } else { (other_start, west_extent) };
+    Self::from_longitude_span(
+      self.tl.latitude.max(other.tl.latitude),
+      self.br.latitude.min(other.br.latitude),
+      left,
+      span
+    )
   }
 
+  /// Largest rectangle covered by both. Returns the default (invalid) rectangle when they do not overlap.
   pub fn intersection(&self, other: &GeoRectangle) -> Self
   {
-    todo!("Implement GeoRectangle::intersection")
+    if !self.valid() || !other.valid() { return GeoRectangle::default() }
+    let (top, bottom) = (self.tl.latitude.min(other.tl.latitude), self.br.latitude.max(other.br.latitude));
+    if top < bottom { return GeoRectangle::default() }
+    let (start, end) = (self.tl.longitude, self.tl.longitude + self.width());
+    let other_start = start + (other.tl.longitude - start).rem_euclid(360.0);
+    let best = [other_start - 360.0, other_start]
+      .into_iter()
+      .map(|s| (s.max(start), (s + other.width()).min(end)))
+      .max_by(|a, b| (a.1 - a.0).total_cmp(&(b.1 - b.0)))
+      .unwrap();
+    if best.1 < best.0 { return GeoRectangle::default() }
+    Self::from_longitude_span(top, bottom, best.0, best.1 - best.0)
+  }
+
+  pub fn area_square_meters(&self) -> Result<f64, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    let band = self.tl.latitude.to_radians().sin() - self.br.latitude.to_radians().sin();
+    Ok((Constants::EARTH_MEAN_RADIUS as f64).powi(2) * self.width().to_radians() * band)
+  }
+
+  pub fn iou(&self, other: &GeoRectangle) -> Result<f64, PositioningError>
+  {
+    let (area, other_area) = (self.area_square_meters()?, other.area_square_meters()?);
+    let intersection = self.intersection(other);
+    let shared = if intersection.valid() { intersection.area_square_meters()? } else { 0.0 };
+    let union = area + other_area - shared;
+    if union <= 0.0 { return Ok(if self == other { 1.0 } else { 0.0 }) }
+    Ok(shared / union)
   }
 
   pub fn translate(&mut self, latitude: f64, longitude: f64)
@@ -243,16 +691,40 @@ impl GeoRectangle
     let c = self.center();
     self.tl = GeoCoordinate::new(
       self.tl.latitude,
-      (c.longitude - width_degrees / 2.0).wrap(Longitude),
+      normalize_longitude(c.longitude - width_degrees / 2.0),
       None
     );
     self.br = GeoCoordinate::new(
       self.br.latitude,
-      (c.longitude + width_degrees / 2.0).wrap(Longitude),
+      normalize_longitude(c.longitude + width_degrees / 2.0),
       None
     );
   }
 
+  /// Multiplies width and height in degrees about the center. Heights are capped at the poles;
+  /// negative or non-finite factors leave the rectangle unchanged.
+  pub fn scaled(&self, factor: f64) -> GeoRectangle
+  {
+    let mut ret = self.clone();
+    if !self.valid() || !factor.is_finite() || factor < 0.0 { return ret }
+    ret.set_width(self.width() * factor);
+    ret.set_height((self.height() * factor).min(180.0));
+    ret
+  }
+
+  /// Expands the width or the height about the center (never shrinking) so that width / height in degrees
+  /// equals `ratio`. Non-positive ratios and degenerate boxes are returned unchanged.
+  pub fn with_aspect_ratio(&self, ratio: f64) -> GeoRectangle
+  {
+    let mut ret = self.clone();
+    if !self.valid() || !ratio.is_finite() || ratio <= 0.0 { return ret }
+    let (width, height) = (self.width(), self.height());
+    if width == 0.0 && height == 0.0 { return ret }
+    if width < height * ratio { ret.set_width(height * ratio) }
+    else { ret.set_height((width / ratio).min(180.0)) }
+    ret
+  }
+
   pub fn set_height(&mut self, height_degrees: f64)
   {
     if !self.valid() { return }
@@ -326,6 +798,103 @@ impl GeoRectangle
     self.tl.latitude == self.br.latitude && self.tl.longitude == self.br.longitude
   }
 
+  fn tile_columns(&self, zoom: u8) -> Vec<u32>
+  {
+    let (left, _) = mercator::tile(self.tl.latitude, self.tl.longitude, zoom);
+    let (right, _) = mercator::tile(self.br.latitude, self.br.longitude, zoom);
+    if left <= right && self.tl.longitude <= self.br.longitude { (left..=right).collect() }
+    else { (left..2u32.pow(zoom as u32)).chain(0..=right).collect() }
+  }
+
+  fn tile_rows(&self, zoom: u8) -> std::ops::RangeInclusive<u32>
+  {
+    let (_, top) = mercator::tile(self.tl.latitude, self.tl.longitude, zoom);
+    let (_, bottom) = mercator::tile(self.br.latitude, self.br.longitude, zoom);
+    top..=bottom
+  }
+
+  // longitude frame centered on the rectangle, so wrapped boxes become contiguous
+  fn from_longitude_span(top: f64, bottom: f64, left: f64, span: f64) -> Self
+  {
+    if span >= 360.0 {
+      return Self::new(GeoCoordinate::new(top, -180.0, None), GeoCoordinate::new(bottom, 180.0, None))
+    }
+    Self::new(
+      GeoCoordinate::new(top, normalize_longitude(left), None),
+      GeoCoordinate::new(bottom, normalize_longitude(left + span), None)
+    )
+  }
+
+  fn check_canvas(&self, pixel_width: u32, pixel_height: u32) -> Result<(), PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
+    if self.width() <= 0.0 || self.height() <= 0.0 {
+      return Err(PositioningError::InvalidArgument("rectangle has zero extent"))
+    }
+    if pixel_width == 0 || pixel_height == 0 { return Err(PositioningError::InvalidArgument("canvas has zero size")) }
+    Ok(())
+  }
+
+  fn frame_longitude(&self, longitude: f64) -> f64
+  {
+    let center = self.tl.longitude + self.width() / 2.0;
+    center + normalize_longitude(longitude - center)
+  }
+
+  fn frame_outcode(&self, latitude: f64, frame_longitude: f64) -> u8
+  {
+    let mut code = Self::OUTCODE_INSIDE;
+    if latitude > self.tl.latitude { code |= Self::OUTCODE_TOP }
+    else if latitude < self.br.latitude { code |= Self::OUTCODE_BOTTOM }
+    if frame_longitude < self.tl.longitude { code |= Self::OUTCODE_LEFT }
+    else if frame_longitude > self.tl.longitude + self.width() { code |= Self::OUTCODE_RIGHT }
+    code
+  }
+
+  fn clip_segment(&self, from: &GeoCoordinate, to: &GeoCoordinate) -> Option<(GeoCoordinate, GeoCoordinate, bool)>
+  {
+    const INSIDE: u8 = GeoRectangle::OUTCODE_INSIDE;
+    const RIGHT: u8 = GeoRectangle::OUTCODE_RIGHT;
+    const BOTTOM: u8 = GeoRectangle::OUTCODE_BOTTOM;
+    const TOP: u8 = GeoRectangle::OUTCODE_TOP;
+
+    let left = self.tl.longitude;
+    let right = left + self.width();
+    let top = self.tl.latitude;
+    let bottom = self.br.latitude;
+    let code = |lat: f64, lon: f64| self.frame_outcode(lat, lon);
+
+    let (y0, y1) = (from.latitude, to.latitude);
+    let x0 = self.frame_longitude(from.longitude);
+    let x1 = x0 + normalize_longitude(to.longitude - from.longitude);
+    let (mut ax, mut ay, mut bx, mut by) = (x0, y0, x1, y1);
+    let (mut ca, mut cb) = (code(ay, ax), code(by, bx));
+    let end_clipped = cb != INSIDE;
+    loop {
+      if ca | cb == INSIDE { break }
+      if ca & cb != INSIDE { return None }
+      let c = if ca != INSIDE { ca } else { cb };
+      let (x, y) = if c & TOP != 0 { (x0 + (x1 - x0) * (top - y0) / (y1 - y0), top) }
+        else if c & BOTTOM != 0 { (x0 + (x1 - x0) * (bottom - y0) / (y1 - y0), bottom) }
+        else if c & RIGHT != 0 { (right, y0 + (y1 - y0) * (right - x0) / (x1 - x0)) }
+        else { (left, y0 + (y1 - y0) * (left - x0) / (x1 - x0)) };
+      if c == ca { ax = x; ay = y; ca = code(ay, ax) }
+      else { bx = x; by = y; cb = code(by, bx) }
+    }
+
+    let point = |lat: f64, lon: f64| -> GeoCoordinate {
+      let t = if (x1 - x0).abs() > (y1 - y0).abs() { (lon - x0) / (x1 - x0) }
+        else if y1 != y0 { (lat - y0) / (y1 - y0) }
+        else { 0.0 };
+      let altitude = match (from.altitude, to.altitude) {
+        (Some(a), Some(b)) => Some(a + (b - a) * t as f32),
+        _ => None
+      };
+      GeoCoordinate::new(lat, normalize_longitude(lon), altitude)
+    };
+    Some((point(ay, ax), point(by, bx), end_clipped))
+  }
+
   fn extend_shape(&mut self, coord: &GeoCoordinate) -> Result<(), PositioningError>
   {
     if !self.valid() { return Err(PositioningError::InvalidGeorectangle(self.clone())) }
@@ -373,9 +942,77 @@ impl GeoRectangle
   }
 }
 
+impl GeoRectangleBuilder
+{
+  pub fn top_left(mut self, coordinate: GeoCoordinate) -> Self
+  {
+    self.tl = Some(coordinate);
+    self
+  }
+
+  pub fn bottom_right(mut self, coordinate: GeoCoordinate) -> Self
+  {
+    self.br = Some(coordinate);
+    self
+  }
+
+  pub fn center(mut self, coordinate: GeoCoordinate) -> Self
+  {
+    self.center = Some(coordinate);
+    self
+  }
+
+  pub fn width_degrees(mut self, width: f64) -> Self
+  {
+    self.width_degrees = Some(width);
+    self
+  }
+
+  pub fn height_degrees(mut self, height: f64) -> Self
+  {
+    self.height_degrees = Some(height);
+    self
+  }
+
+  pub fn build(self) -> Result<GeoRectangle, PositioningError>
+  {
+    let corners = self.tl.is_some() || self.br.is_some();
+    let centered = self.center.is_some() || self.width_degrees.is_some() || self.height_degrees.is_some();
+    let rect = match (corners, centered) {
+      (true, true) => return Err(PositioningError::InvalidBuilderConfiguration(
+        "corners and center/size are mutually exclusive"
+      )),
+      (false, false) => return Err(PositioningError::InvalidBuilderConfiguration(
+        "either corners or center/size must be set"
+      )),
+      (true, false) => {
+        let (Some(tl), Some(br)) = (self.tl, self.br) else {
+          return Err(PositioningError::InvalidBuilderConfiguration("both top left and bottom right must be set"))
+        };
+        if !tl.valid() { return Err(PositioningError::InvalidCoordinate(tl)) }
+        if !br.valid() { return Err(PositioningError::InvalidCoordinate(br)) }
+        GeoRectangle::new(tl, br)
+      }
+      (false, true) => {
+        let (Some(center), Some(width), Some(height)) = (self.center, self.width_degrees, self.height_degrees) else {
+          return Err(PositioningError::InvalidBuilderConfiguration("center, width and height must all be set"))
+        };
+        if !center.valid() { return Err(PositioningError::InvalidCoordinate(center)) }
+        if width < 0.0 || height < 0.0 {
+          return Err(PositioningError::InvalidBuilderConfiguration("width and height must be non-negative"))
+        }
+        GeoRectangle::from_center_degrees(center, width, height)
+      }
+    };
+    if !rect.valid() { return Err(PositioningError::InvalidGeorectangle(rect)) }
+    Ok(rect)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::positioning::GeoPathLengthType;
 
   #[test]
   fn test_default() {
@@ -407,8 +1044,6 @@ mod tests {
     assert_eq!(rect.bottom_right(), GeoCoordinate::new(0.0, 10.0, None));
   }
 
-  // TODO: list ctor test
-
   #[test]
   fn test_assignment() {
     let mut rect1 = GeoRectangle::new(
@@ -453,4 +1088,623 @@ mod tests {
     assert_eq!(rect.bottom_left(), GeoCoordinate::new(-20.0, -20.0, None));
     assert_eq!(rect.bottom_right(), GeoCoordinate::new(-20.0, 30.0, None));
   }
+
+  #[test]
+  fn test_clip_path() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    let path = GeoPath::new(&vec![
+      GeoCoordinate::new(5.0, -5.0, None),
+      GeoCoordinate::new(5.0, 5.0, None),
+      GeoCoordinate::new(5.0, 15.0, None)
+    ]);
+    let parts = rect.clip_path(&path).unwrap();
+    assert_eq!(parts.len(), 1);
+    assert_eq!(parts[0].path(), &vec![
+      GeoCoordinate::new(5.0, 0.0, None),
+      GeoCoordinate::new(5.0, 5.0, None),
+      GeoCoordinate::new(5.0, 10.0, None)
+    ]);
+
+    let outside = GeoPath::new(&vec![
+      GeoCoordinate::new(20.0, -5.0, None),
+      GeoCoordinate::new(20.0, 15.0, None)
+    ]);
+    assert!(rect.clip_path(&outside).unwrap().is_empty());
+  }
+
+  #[test]
+  fn test_clip_path_wrapped() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None)
+    );
+    let path = GeoPath::new(&vec![
+      GeoCoordinate::new(5.0, 160.0, None),
+      GeoCoordinate::new(5.0, 175.0, None),
+      GeoCoordinate::new(5.0, -160.0, None),
+      GeoCoordinate::new(20.0, -160.0, None),
+      GeoCoordinate::new(5.0, -175.0, None)
+    ]);
+    let parts = rect.clip_path(&path).unwrap();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].path(), &vec![
+      GeoCoordinate::new(5.0, 170.0, None),
+      GeoCoordinate::new(5.0, 175.0, None),
+      GeoCoordinate::new(5.0, -170.0, None)
+    ]);
+    assert_eq!(parts[1].size(), 2);
+    assert_eq!(parts[1].at(1).unwrap(), GeoCoordinate::new(5.0, -175.0, None));
+  }
+
+  #[test]
+  fn test_mercator_pixel_size() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    let (w, h) = rect.mercator_pixel_size(10).unwrap();
+    assert!((w - 7281.778).abs() < 1e-3);
+    assert!((h - 7319.031).abs() < 1e-3);
+    let (w, h) = rect.mercator_pixel_size(0).unwrap();
+    assert!((w - 7281.778 / 1024.0).abs() < 1e-6);
+    assert!((h - 7319.031 / 1024.0).abs() < 1e-6);
+
+    let north = GeoRectangle::new(
+      GeoCoordinate::new(60.0, 0.0, None),
+      GeoCoordinate::new(50.0, 10.0, None)
+    );
+    let (w, h) = north.mercator_pixel_size(10).unwrap();
+    assert!((w - 7281.778).abs() < 1e-3);
+    assert!((h - 12778.244).abs() < 1e-3);
+    assert!(GeoRectangle::default().mercator_pixel_size(10).is_err());
+  }
+
+  #[test]
+  fn test_builder_corners() {
+    let rect = GeoRectangle::builder()
+      .top_left(GeoCoordinate::new(10.0, 0.0, None))
+      .bottom_right(GeoCoordinate::new(0.0, 10.0, None))
+      .build()
+      .unwrap();
+    assert_eq!(rect, GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    ));
+    let inverted = GeoRectangle::builder()
+      .top_left(GeoCoordinate::new(0.0, 0.0, None))
+      .bottom_right(GeoCoordinate::new(10.0, 10.0, None))
+      .build();
+    assert!(matches!(inverted, Err(PositioningError::InvalidGeorectangle(_))));
+    let missing = GeoRectangle::builder()
+      .top_left(GeoCoordinate::new(10.0, 0.0, None))
+      .build();
+    assert!(matches!(missing, Err(PositioningError::InvalidBuilderConfiguration(_))));
+  }
+
+  #[test]
+  fn test_builder_center() {
+    let rect = GeoRectangle::builder()
+      .center(GeoCoordinate::new(5.0, 5.0, None))
+      .width_degrees(10.0)
+      .height_degrees(10.0)
+      .build()
+      .unwrap();
+    assert_eq!(rect.top_left(), GeoCoordinate::new(10.0, 0.0, None));
+    assert_eq!(rect.bottom_right(), GeoCoordinate::new(0.0, 10.0, None));
+    let invalid = GeoRectangle::builder()
+      .center(GeoCoordinate::default())
+      .width_degrees(10.0)
+      .height_degrees(10.0)
+      .build();
+    assert!(matches!(invalid, Err(PositioningError::InvalidCoordinate(_))));
+  }
+
+  #[test]
+  fn test_builder_conflict() {
+    let rect = GeoRectangle::builder()
+      .top_left(GeoCoordinate::new(10.0, 0.0, None))
+      .bottom_right(GeoCoordinate::new(0.0, 10.0, None))
+      .center(GeoCoordinate::new(5.0, 5.0, None))
+      .build();
+    assert!(matches!(rect, Err(PositioningError::InvalidBuilderConfiguration(_))));
+    assert!(matches!(GeoRectangle::builder().build(), Err(PositioningError::InvalidBuilderConfiguration(_))));
+  }
+
+  #[test]
+  fn test_tiles_capped() {
+    let large = GeoRectangle::new(
+      GeoCoordinate::new(60.0, -170.0, None),
+      GeoCoordinate::new(-60.0, 170.0, None)
+    );
+    let (large_zoom, tiles) = large.tiles_capped(16).unwrap();
+    assert!(tiles.len() <= 16);
+    assert!(!tiles.is_empty());
+
+    let small = GeoRectangle::new(
+      GeoCoordinate::new(60.5, 30.0, None),
+      GeoCoordinate::new(60.0, 30.5, None)
+    );
+    let (small_zoom, tiles) = small.tiles_capped(16).unwrap();
+    assert!(tiles.len() <= 16);
+    assert!(small_zoom > large_zoom);
+
+    let wrapped = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None)
+    );
+    let (zoom, tiles) = wrapped.tiles_capped(4).unwrap();
+    let last = 2u32.pow(zoom as u32) - 1;
+    assert!(tiles.len() <= 4);
+    assert!(tiles.iter().any(|&(x, _)| x == last));
+    assert!(tiles.iter().any(|&(x, _)| x == 0));
+  }
+
+  #[test]
+  fn test_corners_order() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    assert_eq!(rect.corners(), [
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(10.0, 10.0, None),
+      GeoCoordinate::new(0.0, 10.0, None),
+      GeoCoordinate::new(0.0, 0.0, None)
+    ]);
+    let path = rect.to_path();
+    assert_eq!(path.size(), 5);
+    assert_eq!(path.at(4).unwrap(), path.at(0).unwrap());
+    let perimeter: f32 = rect
+      .corners()
+      .iter()
+      .zip(rect.corners().iter().cycle().skip(1))
+      .map(|(a, b)| a.distance_to(b).unwrap())
+      .sum();
+    assert_eq!(path.length(0, path.size(), GeoPathLengthType::ClosedLoop).unwrap(), perimeter);
+  }
+
+  #[test]
+  fn test_perimeter_meters() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(70.0, 0.0, None),
+      GeoCoordinate::new(60.0, 10.0, None)
+    );
+    let top = rect.top_left().distance_to(&rect.top_right()).unwrap();
+    let bottom = rect.bottom_left().distance_to(&rect.bottom_right()).unwrap();
+    assert!(top < bottom);
+    let perimeter = rect.perimeter_meters().unwrap();
+    assert_eq!(perimeter, top + rect.height_meters().unwrap() + bottom + rect.height_meters().unwrap());
+    assert!(perimeter < 2.0 * (bottom + rect.height_meters().unwrap()));
+    assert!(GeoRectangle::default().perimeter_meters().is_err());
+  }
+
+  #[test]
+  fn test_subdivide() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    let cells = rect.subdivide(2, 2).unwrap();
+    assert_eq!(cells.len(), 4);
+    for cell in &cells {
+      assert_eq!(cell.width(), 5.0);
+      assert_eq!(cell.height(), 5.0);
+      assert!(rect.contains_rect(cell).unwrap());
+    }
+    assert_eq!(cells[0].top_left(), rect.top_left());
+    assert_eq!(cells[3].bottom_right(), rect.bottom_right());
+    assert_eq!(cells[0].center(), GeoCoordinate::new(7.5, 2.5, None));
+    assert_eq!(cells[1].center(), GeoCoordinate::new(7.5, 7.5, None));
+    assert_eq!(cells[2].center(), GeoCoordinate::new(2.5, 2.5, None));
+    assert_eq!(cells[3].center(), GeoCoordinate::new(2.5, 7.5, None));
+    assert!(rect.subdivide(0, 2).is_err());
+    assert!(rect.subdivide(2, 0).is_err());
+  }
+
+  #[test]
+  fn test_subdivide_wrapped() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None)
+    );
+    let cells = rect.subdivide(1, 2).unwrap();
+    assert_eq!(cells[0].top_left(), GeoCoordinate::new(10.0, 170.0, None));
+    assert_eq!(cells[0].bottom_right(), GeoCoordinate::new(0.0, 180.0, None));
+    assert_eq!(cells[1].top_left(), GeoCoordinate::new(10.0, -180.0, None));
+    assert_eq!(cells[1].bottom_right(), GeoCoordinate::new(0.0, -170.0, None));
+  }
+
+  #[test]
+  fn test_outcode() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    assert_eq!(rect.outcode(&GeoCoordinate::new(5.0, 5.0, None)), GeoRectangle::OUTCODE_INSIDE);
+    assert_eq!(rect.outcode(&GeoCoordinate::new(15.0, -5.0, None)), 0b1001);
+    assert_eq!(rect.outcode(&GeoCoordinate::new(-5.0, 15.0, None)), 0b0110);
+    assert_eq!(rect.outcode(&GeoCoordinate::default()), 0b1111);
+
+    let wrapped = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None)
+    );
+    assert_eq!(wrapped.outcode(&GeoCoordinate::new(5.0, 180.0, None)), GeoRectangle::OUTCODE_INSIDE);
+    assert_eq!(wrapped.outcode(&GeoCoordinate::new(5.0, 160.0, None)), GeoRectangle::OUTCODE_LEFT);
+    assert_eq!(wrapped.outcode(&GeoCoordinate::new(5.0, -160.0, None)), GeoRectangle::OUTCODE_RIGHT);
+  }
+
+  #[test]
+  fn test_split() {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    );
+    let (west, east) = rect.split_at_longitude(5.0).unwrap();
+    assert_eq!(west, GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(0.0, 5.0, None)));
+    assert_eq!(east, GeoRectangle::new(GeoCoordinate::new(10.0, 5.0, None), GeoCoordinate::new(0.0, 10.0, None)));
+    let (north, south) = rect.split_at_latitude(5.0).unwrap();
+    assert_eq!(north, GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(5.0, 10.0, None)));
+    assert_eq!(south, GeoRectangle::new(GeoCoordinate::new(5.0, 0.0, None), GeoCoordinate::new(0.0, 10.0, None)));
+    assert!(rect.split_at_longitude(20.0).is_none());
+    assert!(rect.split_at_longitude(10.0).is_none());
+    assert!(rect.split_at_latitude(-5.0).is_none());
+
+    let wrapped = GeoRectangle::new(
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None)
+    );
+    let (west, east) = wrapped.split_at_longitude(180.0).unwrap();
+    assert_eq!(west.bottom_right(), GeoCoordinate::new(0.0, 180.0, None));
+    assert_eq!(east.top_left(), GeoCoordinate::new(10.0, -180.0, None));
+    let (west, east) = wrapped.split_at_longitude(-175.0).unwrap();
+    assert_eq!(west.width(), 15.0);
+    assert_eq!(east.width(), 5.0);
+    assert!(wrapped.split_at_longitude(0.0).is_none());
+  }
+
+  #[test]
+  fn test_expanded_to_square() {
+    for rect in [
+      GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 31.0, None)),
+      GeoRectangle::new(GeoCoordinate::new(1.0, 0.0, None), GeoCoordinate::new(0.0, 10.0, None)),
+      GeoRectangle::new(GeoCoordinate::new(70.0, 170.0, None), GeoCoordinate::new(65.0, -175.0, None))
+    ] {
+      let square = rect.expanded_to_square().unwrap();
+      let (w, h) = (square.width_meters().unwrap(), square.height_meters().unwrap());
+      assert!((w - h).abs() / h < 1e-3);
+      assert!(square.contains_rect(&rect).unwrap());
+    }
+  }
+
+  #[test]
+  fn test_expanded_by_meters() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    let h = rect.height_meters().unwrap();
+    let grown = rect.expanded_by_meters(1000.0).unwrap();
+    assert!((grown.height_meters().unwrap() - (h + 2000.0)).abs() < 1.0);
+    assert!(grown.contains_rect(&rect).unwrap());
+
+    let equatorial = GeoRectangle::new(GeoCoordinate::new(1.0, 30.0, None), GeoCoordinate::new(0.0, 32.0, None));
+    let w = equatorial.width_meters().unwrap();
+    assert!((equatorial.expanded_by_meters(1000.0).unwrap().width_meters().unwrap() - (w + 2000.0)).abs() < 5.0);
+    assert!((equatorial.expanded_by_meters(-1000.0).unwrap().width_meters().unwrap() - (w - 2000.0)).abs() < 5.0);
+
+    let shrunk = rect.expanded_by_meters(-1000.0).unwrap();
+    assert!((shrunk.height_meters().unwrap() - (h - 2000.0)).abs() < 1.0);
+    assert!(rect.contains_rect(&shrunk).unwrap());
+
+    let collapsed = rect.expanded_by_meters(-100_000.0).unwrap();
+    assert!(collapsed.valid() && collapsed.empty());
+    assert_eq!(collapsed.center(), rect.center());
+
+    let polar = GeoRectangle::new(GeoCoordinate::new(89.99, 170.0, None), GeoCoordinate::new(89.0, -170.0, None))
+      .expanded_by_meters(5000.0).unwrap();
+    assert_eq!(polar.top_left().latitude, 90.0);
+    assert!(polar.contains(&GeoCoordinate::new(89.5, 180.0, None)).unwrap());
+  }
+
+  #[test]
+  fn test_contained_fraction() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    let half = GeoPath::new(&vec![GeoCoordinate::new(60.5, 29.0, None), GeoCoordinate::new(60.5, 31.0, None)]);
+    assert!((rect.contained_fraction(&half).unwrap() - 0.5).abs() < 0.01);
+    let inside = GeoPath::new(&vec![GeoCoordinate::new(60.5, 30.5, None), GeoCoordinate::new(60.2, 31.5, None)]);
+    assert!((rect.contained_fraction(&inside).unwrap() - 1.0).abs() < 1e-6);
+    let outside = GeoPath::new(&vec![GeoCoordinate::new(50.0, 30.5, None), GeoCoordinate::new(50.0, 31.5, None)]);
+    assert_eq!(rect.contained_fraction(&outside).unwrap(), 0.0);
+    assert_eq!(rect.contained_fraction(&GeoPath::default()).unwrap(), 0.0);
+  }
+
+  #[test]
+  fn test_from_list() {
+    assert_eq!(GeoRectangle::from_list(&vec![GeoCoordinate::new(60.0, 30.0, None)]), GeoRectangle::default());
+    assert_eq!(GeoRectangle::from_list(&vec![
+      GeoCoordinate::new(60.0, 31.0, None),
+      GeoCoordinate::new(61.0, 30.5, None),
+      GeoCoordinate::new(59.5, 30.0, None)
+    ]), GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(59.5, 31.0, None)));
+    let dateline = GeoRectangle::from_list(&vec![GeoCoordinate::new(0.0, 179.0, None), GeoCoordinate::new(1.0, -179.0, None)]);
+    assert_eq!(dateline, GeoRectangle::new(GeoCoordinate::new(1.0, 179.0, None), GeoCoordinate::new(0.0, -179.0, None)));
+    assert!((dateline.width() - 2.0).abs() < 1e-9);
+    let wide = GeoRectangle::from_list(&vec![
+      GeoCoordinate::new(0.0, -100.0, None),
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(0.0, 100.0, None)
+    ]);
+    assert_eq!(wide.top_left().longitude, -100.0);
+    assert_eq!(wide.bottom_right().longitude, 100.0);
+  }
+
+  #[test]
+  fn test_eq() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 31.0, None));
+    assert_eq!(rect, GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 31.0, None)));
+    assert_ne!(rect, GeoRectangle::new(GeoCoordinate::new(61.0, 30.5, None), GeoCoordinate::new(60.0, 31.5, None)));
+    let invalid = GeoRectangle::new(GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(61.0, 31.0, None));
+    assert_eq!(invalid.width_meters(), Err(PositioningError::InvalidGeorectangle(invalid.clone())));
+  }
+
+  #[test]
+  fn test_csv_bounds() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.5, 30.25, None), GeoCoordinate::new(60.0, 31.0, None));
+    assert_eq!(rect.to_csv_bounds(), "30.25,60,31,61.5");
+    assert_eq!(GeoRectangle::from_csv_bounds(&rect.to_csv_bounds()).unwrap(), rect);
+    assert_eq!(GeoRectangle::from_csv_bounds(" 30.25, 60 ,31,61.5").unwrap(), rect);
+    let wrapped = GeoRectangle::from_csv_bounds("170,-10,-170,10").unwrap();
+    assert_eq!(GeoRectangle::from_csv_bounds(&wrapped.to_csv_bounds()).unwrap(), wrapped);
+    assert!((wrapped.width() - 20.0).abs() < 1e-9);
+
+    for bad in ["", "1,2,3", "1,2,3,4,5", "a,2,3,4", "30,61,31,60", "30,60,181,61", "30,-91,31,61"] {
+      assert!(matches!(GeoRectangle::from_csv_bounds(bad), Err(PositioningError::ParseError(_))), "{}", bad);
+    }
+  }
+
+  #[test]
+  fn test_split_at_antimeridian() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(10.0, 30.0, None), GeoCoordinate::new(0.0, 40.0, None));
+    assert_eq!(rect.split_at_antimeridian(), vec![rect.clone()]);
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(wrapped.split_at_antimeridian(), vec![
+      GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, 180.0, None)),
+      GeoRectangle::new(GeoCoordinate::new(10.0, -180.0, None), GeoCoordinate::new(0.0, -170.0, None))
+    ]);
+    assert!(GeoRectangle::default().split_at_antimeridian().is_empty());
+  }
+
+  #[test]
+  fn test_union_and_intersection() {
+    let a = GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(0.0, 10.0, None));
+    let b = GeoRectangle::new(GeoCoordinate::new(15.0, 5.0, None), GeoCoordinate::new(5.0, 15.0, None));
+    assert_eq!(a.union(&b), GeoRectangle::new(GeoCoordinate::new(15.0, 0.0, None), GeoCoordinate::new(0.0, 15.0, None)));
+    assert_eq!(a.intersection(&b), GeoRectangle::new(GeoCoordinate::new(10.0, 5.0, None), GeoCoordinate::new(5.0, 10.0, None)));
+
+    let east = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, 180.0, None));
+    let west = GeoRectangle::new(GeoCoordinate::new(10.0, -180.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    let wrapped = east.union(&west);
+    assert_eq!(wrapped, GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None)));
+    assert_eq!(wrapped.intersection(&west), west);
+    assert!(!a.intersection(&east).valid());
+  }
+
+  #[test]
+  fn test_iou() {
+    let a = GeoRectangle::new(GeoCoordinate::new(1.0, 0.0, None), GeoCoordinate::new(0.0, 2.0, None));
+    let disjoint = GeoRectangle::new(GeoCoordinate::new(1.0, 5.0, None), GeoCoordinate::new(0.0, 7.0, None));
+    let half = GeoRectangle::new(GeoCoordinate::new(1.0, 1.0, None), GeoCoordinate::new(0.0, 3.0, None));
+    assert_eq!(a.iou(&disjoint).unwrap(), 0.0);
+    assert!((a.iou(&a).unwrap() - 1.0).abs() < 1e-12);
+    assert!((a.iou(&half).unwrap() - 1.0 / 3.0).abs() < 1e-9);
+    assert!(a.iou(&GeoRectangle::default()).is_err());
+  }
+
+  #[test]
+  fn test_nearest_boundary_point() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    assert_eq!(rect.nearest_boundary_point(&GeoCoordinate::new(60.9, 31.0, None)).unwrap(),
+      GeoCoordinate::new(61.0, 31.0, None));
+    assert_eq!(rect.nearest_boundary_point(&GeoCoordinate::new(60.5, 30.1, None)).unwrap(),
+      GeoCoordinate::new(60.5, 30.0, None));
+    assert_eq!(rect.nearest_boundary_point(&GeoCoordinate::new(62.0, 29.0, None)).unwrap(),
+      GeoCoordinate::new(61.0, 30.0, None));
+    assert_eq!(rect.nearest_boundary_point(&GeoCoordinate::new(59.0, 31.0, None)).unwrap(),
+      GeoCoordinate::new(60.0, 31.0, None));
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(wrapped.nearest_boundary_point(&GeoCoordinate::new(5.0, -160.0, None)).unwrap(),
+      GeoCoordinate::new(5.0, -170.0, None));
+    assert_eq!(wrapped.nearest_boundary_point(&GeoCoordinate::new(5.0, -171.0, None)).unwrap(),
+      GeoCoordinate::new(5.0, -170.0, None));
+    assert_eq!(wrapped.nearest_boundary_point(&GeoCoordinate::new(15.0, 179.0, None)).unwrap(),
+      GeoCoordinate::new(10.0, 179.0, None));
+    assert!(wrapped.nearest_boundary_point(&GeoCoordinate::default()).is_err());
+  }
+
+  #[test]
+  fn test_contains_with() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 31.0, None));
+    let top = GeoCoordinate::new(61.0, 30.5, None);
+    assert!(rect.contains_with(&top, true).unwrap());
+    assert!(!rect.contains_with(&top, false).unwrap());
+    assert!(!rect.contains_with(&GeoCoordinate::new(60.5, 31.0, None), false).unwrap());
+    assert!(rect.contains_with(&GeoCoordinate::new(60.0, 30.0, None), false).unwrap());
+    assert!(rect.contains(&GeoCoordinate::new(60.5, 31.0, None)).unwrap());
+
+    let west = GeoRectangle::new(GeoCoordinate::new(61.0, 29.0, None), GeoCoordinate::new(60.0, 30.0, None));
+    let border = GeoCoordinate::new(60.5, 30.0, None);
+    assert!(west.contains_with(&border, false).unwrap() != rect.contains_with(&border, false).unwrap());
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert!(wrapped.contains_with(&GeoCoordinate::new(5.0, -171.0, None), false).unwrap());
+    assert!(!wrapped.contains_with(&GeoCoordinate::new(5.0, -170.0, None), false).unwrap());
+  }
+
+  #[test]
+  fn test_grown_to_min() {
+    let point = GeoRectangle::new(GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(60.0, 30.0, None));
+    let grown = point.grown_to_min(1000.0, 500.0).unwrap();
+    assert!((grown.width_meters().unwrap() - 1000.0).abs() < 0.5);
+    assert!((grown.height_meters().unwrap() - 500.0).abs() < 0.5);
+    assert_eq!(grown.center(), point.center());
+
+    let large = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    assert_eq!(large.grown_to_min(1000.0, 500.0).unwrap(), large);
+
+    let narrow = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 30.001, None));
+    let widened = narrow.grown_to_min(5000.0, 500.0).unwrap();
+    assert!((widened.width_meters().unwrap() - 5000.0).abs() < 0.5);
+    assert_eq!(widened.height(), narrow.height());
+  }
+
+  #[test]
+  fn test_intersects() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(10.0, 0.0, None), GeoCoordinate::new(0.0, 10.0, None));
+    assert!(rect.intersects(&GeoRectangle::new(GeoCoordinate::new(15.0, 5.0, None), GeoCoordinate::new(5.0, 15.0, None))));
+    assert!(rect.intersects(&GeoRectangle::new(GeoCoordinate::new(5.0, 10.0, None), GeoCoordinate::new(0.0, 20.0, None))));
+    assert!(!rect.intersects(&GeoRectangle::new(GeoCoordinate::new(5.0, 11.0, None), GeoCoordinate::new(0.0, 20.0, None))));
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    let overlapping = GeoRectangle::new(GeoCoordinate::new(5.0, 175.0, None), GeoCoordinate::new(-5.0, -175.0, None));
+    let other_band = GeoRectangle::new(GeoCoordinate::new(-20.0, 100.0, None), GeoCoordinate::new(-30.0, -100.0, None));
+    assert!(wrapped.intersects(&overlapping));
+    assert!(overlapping.intersects(&wrapped));
+    assert!(!wrapped.intersects(&other_band));
+    assert!(!wrapped.intersects(&rect));
+    assert!(wrapped.intersects(&GeoRectangle::new(GeoCoordinate::new(5.0, -175.0, None), GeoCoordinate::new(0.0, -160.0, None))));
+
+    let point = |lon: f64| GeoRectangle::new(GeoCoordinate::new(5.0, lon, None), GeoCoordinate::new(5.0, lon, None));
+    assert!(!point(20.0).intersects(&point(50.0)));
+    assert!(point(20.0).intersects(&point(20.0)));
+    assert!(!point(20.0).intersects(&wrapped));
+    assert!(point(180.0).intersects(&wrapped));
+  }
+
+  #[test]
+  fn test_contains_circle() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    let center = GeoCoordinate::new(60.5, 31.0, None);
+    assert!(rect.contains_circle(&GeoCircle::new(center, 10000.0)).unwrap());
+    let near_edge = GeoCoordinate::new(60.5, 30.1, None);
+    assert!(!rect.contains_circle(&GeoCircle::new(near_edge, 10000.0)).unwrap());
+    assert!(rect.contains_circle(&GeoCircle::new(near_edge, 5000.0)).unwrap());
+    assert!(!rect.contains_circle(&GeoCircle::new(center, 60000.0)).unwrap());
+    assert!(rect.contains_circle(&GeoCircle::default()).is_err());
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert!(wrapped.contains_circle(&GeoCircle::new(GeoCoordinate::new(5.0, 180.0, None), 100000.0)).unwrap());
+    assert!(!wrapped.contains_circle(&GeoCircle::new(GeoCoordinate::new(5.0, -171.0, None), 200000.0)).unwrap());
+  }
+
+  #[test]
+  fn test_center() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    assert_eq!(rect.center(), GeoCoordinate::new(60.5, 31.0, None));
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(wrapped.center().latitude, 5.0);
+    assert_eq!(wrapped.center().longitude.abs(), 180.0);
+    assert!(wrapped.contains(&wrapped.center()).unwrap());
+
+    let lopsided = GeoRectangle::new(GeoCoordinate::new(10.0, 100.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(lopsided.center(), GeoCoordinate::new(5.0, 145.0, None));
+    assert!(lopsided.contains(&lopsided.center()).unwrap());
+    let west_heavy = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -100.0, None));
+    assert_eq!(west_heavy.center(), GeoCoordinate::new(5.0, -145.0, None));
+  }
+
+  #[test]
+  fn test_scaled() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    let doubled = rect.scaled(2.0);
+    assert!((doubled.width() - 2.0 * rect.width()).abs() < 1e-9);
+    assert!((doubled.height() - 2.0 * rect.height()).abs() < 1e-9);
+    assert_eq!(doubled.center(), rect.center());
+    let halved = rect.scaled(0.5);
+    assert!((halved.width() - 1.0).abs() < 1e-9);
+    assert_eq!(halved.center(), rect.center());
+    assert_eq!(rect.scaled(-1.0), rect);
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    let grown = wrapped.scaled(2.0);
+    assert!((grown.width() - 40.0).abs() < 1e-9);
+    assert_eq!(grown.top_left().longitude, 160.0);
+    assert!(grown.valid());
+
+    let polar = GeoRectangle::new(GeoCoordinate::new(85.0, 0.0, None), GeoCoordinate::new(75.0, 10.0, None)).scaled(3.0);
+    assert!(polar.valid());
+    assert!(polar.top_left().latitude <= 90.0);
+    assert_eq!(polar.center().latitude, 80.0);
+  }
+
+  #[test]
+  fn test_map_to_pixel() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(61.0, 30.0, None), GeoCoordinate::new(60.0, 32.0, None));
+    assert_eq!(rect.map_to_pixel(&rect.top_left(), 800, 400).unwrap(), (0.0, 0.0));
+    assert_eq!(rect.map_to_pixel(&rect.bottom_right(), 800, 400).unwrap(), (800.0, 400.0));
+    assert_eq!(rect.map_to_pixel(&rect.top_right(), 800, 400).unwrap(), (800.0, 0.0));
+    assert_eq!(rect.map_to_pixel(&rect.bottom_left(), 800, 400).unwrap(), (0.0, 400.0));
+    for coordinate in [GeoCoordinate::new(60.25, 31.5, None), GeoCoordinate::new(62.0, 29.0, None)] {
+      let (x, y) = rect.map_to_pixel(&coordinate, 800, 400).unwrap();
+      assert_eq!(rect.pixel_to_map(x, y, 800, 400).unwrap(), coordinate);
+    }
+
+    let wrapped = GeoRectangle::new(GeoCoordinate::new(10.0, 170.0, None), GeoCoordinate::new(0.0, -170.0, None));
+    assert_eq!(wrapped.map_to_pixel(&GeoCoordinate::new(5.0, -175.0, None), 200, 100).unwrap(), (150.0, 50.0));
+    assert_eq!(wrapped.pixel_to_map(150.0, 50.0, 200, 100).unwrap(), GeoCoordinate::new(5.0, -175.0, None));
+    assert!(rect.map_to_pixel(&rect.center(), 0, 400).is_err());
+    assert!(GeoRectangle::new(rect.top_left(), rect.top_left()).map_to_pixel(&rect.center(), 800, 400).is_err());
+  }
+
+  #[test]
+  fn test_contains_2d_3d() {
+    let flat = GeoRectangle::new(GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(50.0, 40.0, None));
+    let high = GeoCoordinate::new(55.0, 35.0, Some(10000.0));
+    let ground = GeoCoordinate::new(55.0, 35.0, None);
+    assert!(flat.contains_2d(&high).unwrap());
+    assert!(flat.contains_3d(&high).unwrap());
+    assert!(flat.contains_3d(&ground).unwrap());
+
+    let volume = GeoRectangle::new(GeoCoordinate::new(60.0, 30.0, Some(0.0)), GeoCoordinate::new(50.0, 40.0, Some(500.0)));
+    assert!(volume.contains_2d(&high).unwrap());
+    assert!(volume.contains(&high).unwrap());
+    assert!(!volume.contains_3d(&high).unwrap());
+    assert!(!volume.contains_3d(&ground).unwrap());
+    assert!(volume.contains_3d(&GeoCoordinate::new(55.0, 35.0, Some(250.0))).unwrap());
+    assert!(!volume.contains_3d(&GeoCoordinate::new(65.0, 35.0, Some(250.0))).unwrap());
+  }
+
+  #[test]
+  fn test_from_center_meters_exact() {
+    let center = GeoCoordinate::new(55.75, 37.62, None);
+    let rect = GeoRectangle::from_center_meters_exact(center, 1000.0, 500.0).unwrap();
+    assert!((rect.width_meters().unwrap() - 1000.0).abs() < 1.0);
+    assert!((rect.height_meters().unwrap() - 500.0).abs() < 1.0);
+    assert!((rect.center().latitude - center.latitude).abs() < 1e-9);
+    assert!((rect.center().longitude - center.longitude).abs() < 1e-9);
+
+    let dateline = GeoRectangle::from_center_meters_exact(GeoCoordinate::new(0.0, 180.0, None), 2000.0, 2000.0).unwrap();
+    assert!((dateline.width_meters().unwrap() - 2000.0).abs() < 1.0);
+    assert!(dateline.contains(&GeoCoordinate::new(0.0, -179.995, None)).unwrap());
+
+    assert!(GeoRectangle::from_center_meters_exact(GeoCoordinate::new(89.99, 0.0, None), 10.0, 5000.0).is_err());
+    assert!(GeoRectangle::from_center_meters_exact(center, -1.0, 10.0).is_err());
+  }
+
+  #[test]
+  fn test_with_aspect_ratio() {
+    let rect = GeoRectangle::new(GeoCoordinate::new(20.0, 10.0, None), GeoCoordinate::new(10.0, 20.0, None));
+    let wide = rect.with_aspect_ratio(2.0);
+    assert!((wide.width() - 20.0).abs() < 1e-9);
+    assert!((wide.height() - 10.0).abs() < 1e-9);
+    assert_eq!(wide.center(), rect.center());
+    let tall = rect.with_aspect_ratio(0.5);
+    assert!((tall.width() - 10.0).abs() < 1e-9);
+    assert!((tall.height() - 20.0).abs() < 1e-9);
+    assert_eq!(tall.center(), rect.center());
+    assert_eq!(rect.with_aspect_ratio(1.0), rect);
+    assert_eq!(rect.with_aspect_ratio(0.0), rect);
+    assert_eq!(rect.with_aspect_ratio(-2.0), rect);
+  }
 }
\ No newline at end of file
@@ -0,0 +1,409 @@
+use std::fmt::Display;
+use crate::positioning::constants as Constants;
+use crate::positioning::errors::PositioningError;
+use crate::positioning::{GeoCoordinate, GeoPath, GeoRectangle};
+use crate::positioning::utility::normalize_longitude;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeoCircle
+{
+  center: GeoCoordinate,
+  radius_meters: f32
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct GeoPolygon
+{
+  ring: Vec<GeoCoordinate>
+}
+
+impl Default for GeoCircle
+{
+  fn default() -> Self
+  {
+    Self { center: GeoCoordinate::default(), radius_meters: -1.0 }
+  }
+}
+
+impl Display for GeoCircle
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(f, "[{}, {:.2}m]", self.center, self.radius_meters)
+  }
+}
+
+impl GeoCircle
+{
+  pub fn new(center: GeoCoordinate, radius_meters: f32) -> Self
+  {
+    Self { center, radius_meters }
+  }
+
+  pub fn center(&self) -> GeoCoordinate { self.center }
+  pub fn radius_meters(&self) -> f32 { self.radius_meters }
+
+  pub fn valid(&self) -> bool
+  {
+    self.center.valid() && self.radius_meters.is_finite() && self.radius_meters >= 0.0
+  }
+
+  pub fn contains(&self, coordinate: &GeoCoordinate) -> Result<bool, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeocircle(self.clone())) }
+    Ok(self.center.distance_to(coordinate)? <= self.radius_meters)
+  }
+
+  pub fn signed_distance_to(&self, coordinate: &GeoCoordinate) -> Result<f32, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeocircle(self.clone())) }
+    Ok(self.center.distance_to(coordinate)? - self.radius_meters)
+  }
+
+  pub fn bounding_georectangle(&self) -> Result<GeoRectangle, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeocircle(self.clone())) }
+    GeoRectangle::from_center_meters(self.center, self.radius_meters * 2.0, self.radius_meters * 2.0)
+  }
+
+  pub fn clipped_outline(&self, rect: &GeoRectangle, segments: usize) -> Result<GeoPath, PositioningError>
+  {
+    if !self.valid() { return Err(PositioningError::InvalidGeocircle(self.clone())) }
+    let mut outline = self.to_polygon(segments);
+    outline.close_loop();
+    let mut parts = rect.clip_path(&outline)?;
+    if parts.len() > 1 {
+      // the ring starts at north, so an arc crossing it is split into a tail and a head
+      let (head, tail) = (parts.remove(0), parts.pop().unwrap());
+      if tail.path().last() == head.path().first() {
+        let mut arc = tail;
+        arc.append(&GeoPath::new(&head.path()[1..].to_vec()));
+        parts.push(arc);
+      } else {
+        parts.insert(0, head);
+        parts.push(tail);
+      }
+    }
+    Ok(parts
+      .into_iter()
+      .max_by_key(|part| part.size())
+      .unwrap_or_default())
+  }
+
+  pub fn to_polygon(&self, segments: usize) -> GeoPath
+  {
+    if !self.valid() { return GeoPath::default() }
+    let path = (0..segments)
+      .filter_map(|i| self.center
+        .at_distance_and_azimuth(self.radius_meters, 360.0 * i as f32 / segments as f32)
+        .ok()
+      ).collect();
+    GeoPath::new(&path)
+  }
+}
+
+impl Display for GeoPolygon
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    write!(f, "[")?;
+    for coordinate in &self.ring {
+      write!(f, "{}", coordinate)?;
+    }
+    write!(f, "]")
+  }
+}
+
+impl GeoPolygon
+{
+  pub fn new(ring: &[GeoCoordinate]) -> Result<Self, PositioningError>
+  {
+    let mut ring = ring.to_vec();
+    if ring.len() > 1 && ring.first() == ring.last() { ring.pop(); }
+    if ring.len() < 3 { return Err(PositioningError::NotEnoughPoints { needed: 3, got: ring.len() }) }
+    ring.push(ring[0]);
+    Ok(Self { ring })
+  }
+
+  pub fn ring(&self) -> &Vec<GeoCoordinate>
+  {
+    &self.ring
+  }
+
+  pub fn contains(&self, coordinate: &GeoCoordinate) -> Result<bool, PositioningError>
+  {
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+    let vertices = self.unwrapped()?;
+    if vertices.len() < 4 { return Ok(false) }
+    let (lat, lon) = (coordinate.latitude, Self::unwrap_longitude(coordinate.longitude, vertices[0].1));
+    let mut inside = false;
+    for w in vertices.windows(2) {
+      let ((lat1, lon1), (lat2, lon2)) = (w[0], w[1]);
+      if (lat1 > lat) != (lat2 > lat) && lon < lon1 + (lat - lat1) / (lat2 - lat1) * (lon2 - lon1) {
+        inside = !inside
+      }
+    }
+    Ok(inside)
+  }
+
+  pub fn area_square_meters(&self) -> Result<f64, PositioningError>
+  {
+    let vertices = self.unwrapped()?;
+    if vertices.len() < 4 {
+      return Err(PositioningError::NotEnoughPoints { needed: 3, got: vertices.len().saturating_sub(1) })
+    }
+    let excess: f64 = vertices
+      .windows(2)
+      .map(|w| (w[1].1 - w[0].1).to_radians()
+        * (2.0 + w[0].0.to_radians().sin() + w[1].0.to_radians().sin())
+      ).sum();
+    Ok((excess / 2.0).abs() * (Constants::EARTH_MEAN_RADIUS as f64).powi(2))
+  }
+
+  pub fn bounding_georectangle(&self) -> Result<GeoRectangle, PositioningError>
+  {
+    let vertices = self.unwrapped()?;
+    if vertices.is_empty() { return Ok(GeoRectangle::default()) }
+    let (mut top, mut bottom) = (f64::MIN, f64::MAX);
+    let (mut left, mut right) = (f64::MAX, f64::MIN);
+    for (lat, lon) in vertices {
+      top = top.max(lat);
+      bottom = bottom.min(lat);
+      left = left.min(lon);
+      right = right.max(lon);
+    }
+    if right - left >= 360.0 { (left, right) = (-180.0, 180.0) }
+    Ok(GeoRectangle::new(
+      GeoCoordinate::new(top, Self::unwrap_longitude(left, 0.0), None),
+      GeoCoordinate::new(bottom, Self::unwrap_longitude(right, 0.0), None)
+    ))
+  }
+
+  fn unwrapped(&self) -> Result<Vec<(f64, f64)>, PositioningError>
+  {
+    let Some(first) = self.ring.first() else { return Ok(Vec::new()) };
+    self.ring
+      .iter()
+      .map(|c| {
+        if !c.valid() { return Err(PositioningError::InvalidCoordinate(*c)) }
+        Ok((c.latitude, Self::unwrap_longitude(c.longitude, first.longitude)))
+      }).collect()
+  }
+
+  fn unwrap_longitude(longitude: f64, reference: f64) -> f64
+  {
+    reference + normalize_longitude(longitude - reference)
+  }
+}
+
+/// Closed polygon around the path at `half_width_meters` on each side, with mitered joins and rounded ends.
+pub fn corridor(path: &GeoPath, half_width_meters: f32) -> Result<GeoPolygon, PositioningError>
+{
+  const CAP_SEGMENTS: usize = 8;
+  const MAX_MITER: f32 = 4.0;
+
+  if half_width_meters.is_nan() || half_width_meters <= 0.0 {
+    return Err(PositioningError::InvalidArgument("corridor half-width must be positive"))
+  }
+  let points = path.path();
+  if points.len() < 2 { return Err(PositioningError::NotEnoughPoints { needed: 2, got: points.len() }) }
+
+  let mut headings = Vec::with_capacity(points.len());
+  let mut miters = Vec::with_capacity(points.len());
+  for i in 0..points.len() {
+    let outgoing = if i + 1 < points.len() { Some(points[i].azimuth_to(&points[i + 1])?) } else { None };
+    let incoming = if i > 0 { Some((points[i].azimuth_to(&points[i - 1])? + 180.0) % 360.0) } else { None };
+    let (heading, half_turn) = match (incoming, outgoing) {
+      (Some(a), Some(b)) => {
+        let (a, b) = (a.to_radians(), b.to_radians());
+        let heading = (a.sin() + b.sin()).atan2(a.cos() + b.cos()).to_degrees();
+        let turn = ((b - a).sin()).atan2((b - a).cos());
+        (heading, turn / 2.0)
+      },
+      (Some(a), None) | (None, Some(a)) => (a, 0.0),
+      (None, None) => unreachable!()
+    };
+    headings.push(heading);
+    miters.push(half_width_meters / half_turn.cos().max(1.0 / MAX_MITER));
+  }
+
+  let last = points.len() - 1;
+  let mut ring = Vec::new();
+  for i in 0..points.len() {
+    ring.push(points[i].at_distance_and_azimuth(miters[i], headings[i] - 90.0)?);
+  }
+  for k in 1..CAP_SEGMENTS {
+    let azimuth = headings[last] - 90.0 + 180.0 * k as f32 / CAP_SEGMENTS as f32;
+    ring.push(points[last].at_distance_and_azimuth(half_width_meters, azimuth)?);
+  }
+  for i in (0..points.len()).rev() {
+    ring.push(points[i].at_distance_and_azimuth(miters[i], headings[i] + 90.0)?);
+  }
+  for k in 1..CAP_SEGMENTS {
+    let azimuth = headings[0] + 90.0 + 180.0 * k as f32 / CAP_SEGMENTS as f32;
+    ring.push(points[0].at_distance_and_azimuth(half_width_meters, azimuth)?);
+  }
+  GeoPolygon::new(&ring)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn square() -> GeoPolygon
+  {
+    GeoPolygon::new(&[
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None),
+      GeoCoordinate::new(10.0, 10.0, None),
+      GeoCoordinate::new(10.0, 0.0, None)
+    ]).unwrap()
+  }
+
+  #[test]
+  fn test_circle_contains() {
+    let circle = GeoCircle::new(GeoCoordinate::new(60.0, 30.0, None), 1000.0);
+    assert!(circle.contains(&GeoCoordinate::new(60.0, 30.0, None)).unwrap());
+    let inside = GeoCoordinate::new(60.0, 30.0, None).at_distance_and_azimuth(999.0, 45.0).unwrap();
+    assert!(circle.contains(&inside).unwrap());
+    let outside = GeoCoordinate::new(60.0, 30.0, None).at_distance_and_azimuth(1001.0, 45.0).unwrap();
+    assert!(!circle.contains(&outside).unwrap());
+    assert!(GeoCircle::new(GeoCoordinate::new(60.0, 30.0, None), -1.0).contains(&inside).is_err());
+    assert!(GeoCircle::default().contains(&inside).is_err());
+  }
+
+  #[test]
+  fn test_circle_bounding_georectangle() {
+    let circle = GeoCircle::new(GeoCoordinate::new(60.0, 30.0, None), 1000.0);
+    let rect = circle.bounding_georectangle().unwrap();
+    assert!(rect.contains(&circle.center()).unwrap());
+    assert!((rect.height_meters().unwrap() - 2000.0).abs() < 1.0);
+  }
+
+  #[test]
+  fn test_circle_to_polygon() {
+    let circle = GeoCircle::new(GeoCoordinate::new(60.0, 30.0, None), 1000.0);
+    let polygon = circle.to_polygon(36);
+    assert_eq!(polygon.size(), 36);
+    for coordinate in polygon.iter() {
+      assert!((circle.center().distance_to(coordinate).unwrap() - 1000.0).abs() < 0.1);
+    }
+  }
+
+  #[test]
+  fn test_polygon_contains() {
+    let polygon = square();
+    assert_eq!(polygon.ring().len(), 5);
+    assert!(polygon.contains(&GeoCoordinate::new(5.0, 5.0, None)).unwrap());
+    assert!(!polygon.contains(&GeoCoordinate::new(15.0, 5.0, None)).unwrap());
+    assert!(!polygon.contains(&GeoCoordinate::new(5.0, -5.0, None)).unwrap());
+    assert!(polygon.contains(&GeoCoordinate::default()).is_err());
+  }
+
+  #[test]
+  fn test_polygon_antimeridian() {
+    let polygon = GeoPolygon::new(&[
+      GeoCoordinate::new(0.0, 170.0, None),
+      GeoCoordinate::new(0.0, -170.0, None),
+      GeoCoordinate::new(10.0, -170.0, None),
+      GeoCoordinate::new(10.0, 170.0, None)
+    ]).unwrap();
+    assert!(polygon.contains(&GeoCoordinate::new(5.0, 175.0, None)).unwrap());
+    assert!(polygon.contains(&GeoCoordinate::new(5.0, -175.0, None)).unwrap());
+    assert!(!polygon.contains(&GeoCoordinate::new(5.0, 0.0, None)).unwrap());
+    assert!(!polygon.contains(&GeoCoordinate::new(5.0, 160.0, None)).unwrap());
+    let rect = polygon.bounding_georectangle().unwrap();
+    assert_eq!(rect.top_left(), GeoCoordinate::new(10.0, 170.0, None));
+    assert_eq!(rect.bottom_right(), GeoCoordinate::new(0.0, -170.0, None));
+    assert!((polygon.area_square_meters().unwrap() / square().area_square_meters().unwrap() - 2.0).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_polygon_area() {
+    let polygon = GeoPolygon::new(&[
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(0.0, 1.0, None),
+      GeoCoordinate::new(1.0, 1.0, None),
+      GeoCoordinate::new(1.0, 0.0, None)
+    ]).unwrap();
+    let expected = 1.2363e10;
+    assert!((polygon.area_square_meters().unwrap() - expected).abs() / expected < 1e-3);
+    assert_eq!(square().bounding_georectangle().unwrap(), GeoRectangle::new(
+      GeoCoordinate::new(10.0, 0.0, None),
+      GeoCoordinate::new(0.0, 10.0, None)
+    ));
+  }
+
+  #[test]
+  fn test_circle_clipped_outline() {
+    let circle = GeoCircle::new(GeoCoordinate::new(0.0, 0.0, None), 100000.0);
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(5.0, 0.0, None),
+      GeoCoordinate::new(-5.0, 5.0, None)
+    );
+    let arc = circle.clipped_outline(&rect, 36).unwrap();
+    assert_eq!(arc.size(), 19);
+    assert_ne!(arc.at(0).unwrap(), arc.at(arc.size() - 1).unwrap());
+    for coordinate in arc.iter() {
+      assert!(coordinate.longitude >= 0.0);
+    }
+    assert!((arc.at(0).unwrap().longitude).abs() < 1e-9);
+    assert!((arc.at(arc.size() - 1).unwrap().longitude).abs() < 1e-9);
+
+    let enclosing = GeoRectangle::new(
+      GeoCoordinate::new(5.0, -5.0, None),
+      GeoCoordinate::new(-5.0, 5.0, None)
+    );
+    assert_eq!(circle.clipped_outline(&enclosing, 36).unwrap().size(), 37);
+  }
+
+  #[test]
+  fn test_polygon_not_enough_points() {
+    let two = [GeoCoordinate::new(0.0, 0.0, None), GeoCoordinate::new(0.0, 1.0, None)];
+    assert!(matches!(GeoPolygon::new(&two), Err(PositioningError::NotEnoughPoints { needed: 3, got: 2 })));
+    let closed_two = [two[0], two[1], two[0]];
+    assert!(matches!(GeoPolygon::new(&closed_two), Err(PositioningError::NotEnoughPoints { needed: 3, got: 2 })));
+    assert!(matches!(GeoPolygon::default().area_square_meters(),
+      Err(PositioningError::NotEnoughPoints { needed: 3, got: 0 })));
+    let closed = [two[0], two[1], GeoCoordinate::new(1.0, 1.0, None), two[0]];
+    assert_eq!(GeoPolygon::new(&closed).unwrap().ring().len(), 4);
+  }
+
+  #[test]
+  fn test_circle_signed_distance_to() {
+    let center = GeoCoordinate::new(60.0, 30.0, None);
+    let circle = GeoCircle::new(center, 1000.0);
+    assert_eq!(circle.signed_distance_to(&center).unwrap(), -1000.0);
+    let inside = center.at_distance_and_azimuth(400.0, 90.0).unwrap();
+    assert!((circle.signed_distance_to(&inside).unwrap() + 600.0).abs() < 0.1);
+    let edge = center.at_distance_and_azimuth(1000.0, 200.0).unwrap();
+    assert!(circle.signed_distance_to(&edge).unwrap().abs() < 0.1);
+    let outside = center.at_distance_and_azimuth(2500.0, 0.0).unwrap();
+    assert!((circle.signed_distance_to(&outside).unwrap() - 1500.0).abs() < 0.1);
+    assert!(GeoCircle::default().signed_distance_to(&center).is_err());
+  }
+
+  #[test]
+  fn test_corridor() {
+    let path = GeoPath::new(&vec![
+      GeoCoordinate::new(60.0, 30.0, None),
+      GeoCoordinate::new(60.0, 30.1, None),
+      GeoCoordinate::new(60.05, 30.15, None)
+    ]);
+    let polygon = corridor(&path, 500.0).unwrap();
+    for coordinate in path.iter() {
+      assert!(polygon.contains(coordinate).unwrap());
+    }
+    let middle = GeoCoordinate::new(60.0, 30.05, None);
+    assert!(polygon.contains(&middle.at_distance_and_azimuth(400.0, 0.0).unwrap()).unwrap());
+    assert!(polygon.contains(&middle.at_distance_and_azimuth(400.0, 180.0).unwrap()).unwrap());
+    assert!(!polygon.contains(&middle.at_distance_and_azimuth(600.0, 0.0).unwrap()).unwrap());
+    assert!(!polygon.contains(&middle.at_distance_and_azimuth(600.0, 180.0).unwrap()).unwrap());
+    assert!(polygon.contains(&path.at(0).unwrap().at_distance_and_azimuth(400.0, 270.0).unwrap()).unwrap());
+    assert!(!polygon.contains(&path.at(0).unwrap().at_distance_and_azimuth(600.0, 270.0).unwrap()).unwrap());
+    assert!(polygon.contains(&path.at(1).unwrap().at_distance_and_azimuth(400.0, 135.0).unwrap()).unwrap());
+
+    assert!(corridor(&path, 0.0).is_err());
+    assert!(matches!(corridor(&GeoPath::default(), 10.0), Err(PositioningError::NotEnoughPoints { needed: 2, got: 0 })));
+  }
+}
\ No newline at end of file
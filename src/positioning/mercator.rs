@@ -0,0 +1,29 @@
+use crate::positioning::constants as Constants;
+
+pub(crate) const TILE_SIZE: f64 = 256.0;
+pub(crate) const MAX_ZOOM: u8 = 22;
+
+pub(crate) fn world_size(zoom: u8) -> f64
+{
+  TILE_SIZE * 2.0f64.powi(zoom as i32)
+}
+
+pub(crate) fn normalized_x(longitude: f64) -> f64
+{
+  (longitude + 180.0) / 360.0
+}
+
+pub(crate) fn normalized_y(latitude: f64) -> f64
+{
+  let lat = latitude
+    .clamp(-Constants::MERCATOR_MAX_LATITUDE, Constants::MERCATOR_MAX_LATITUDE)
+    .to_radians();
+  (1.0 - lat.tan().asinh() / std::f64::consts::PI) / 2.0
+}
+
+pub(crate) fn tile(latitude: f64, longitude: f64, zoom: u8) -> (u32, u32)
+{
+  let n = 2u32.pow(zoom as u32);
+  let to_tile = |t: f64| ((t * n as f64).floor().max(0.0) as u32).min(n - 1);
+  (to_tile(normalized_x(longitude)), to_tile(normalized_y(latitude)))
+}
\ No newline at end of file
@@ -1,17 +1,31 @@
 pub mod errors;
 pub mod constants;
 mod utility;
+mod mercator;
+mod ellipsoid;
 
 mod coordinate;
 mod path;
 mod georectangle;
 mod geoshape;
+mod projection;
 
 pub use utility::CardinalDirection;
 pub use utility::CoordinateField;
 pub use utility::CoordinateFieldType;
+pub use utility::DistanceUnit;
+pub use ellipsoid::Ellipsoid;
 pub use coordinate::GeoCoordinate;
 pub use coordinate::GeoCoordinateType;
+pub use coordinate::QuantizedCoordinate;
 pub use path::GeoPath;
 pub use path::GeoPathLengthType;
-pub use georectangle::GeoRectangle;
\ No newline at end of file
+pub use path::GeoPathDistanceMode;
+pub use georectangle::GeoRectangle;
+pub use georectangle::GeoRectangleBuilder;
+pub use geoshape::GeoCircle;
+pub use geoshape::GeoPolygon;
+pub use geoshape::corridor;
+pub use projection::Projection;
+pub use projection::WebMercator;
+pub use projection::PlateCarree;
\ No newline at end of file
@@ -1,6 +1,10 @@
 use std::fmt::Display;
+use std::ops::Index;
+use std::sync::atomic::{AtomicU32, Ordering};
+use crate::positioning::constants as Constants;
 use crate::positioning::errors::PositioningError;
-use crate::positioning::GeoCoordinate;
+use crate::positioning::utility::normalize_longitude;
+use crate::positioning::{GeoCoordinate, GeoRectangle, Projection};
 
 pub enum GeoPathLengthType
 {
@@ -8,20 +12,51 @@ pub enum GeoPathLengthType
   ClosedLoop
 }
 
-#[derive(Debug, Clone)]
-pub struct GeoPath
+/// How consecutive vertices are joined when measuring a path: shortest great-circle arcs, or
+/// constant-bearing rhumb lines (straight segments on a Mercator chart).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeoPathDistanceMode
+{
+  GreatCircle,
+  RhumbLine
+}
+
+/// Cached total length, stored as `f32` bits with NaN meaning "not computed". Atomic so that
+/// `GeoPath` stays `Sync`.
+#[derive(Debug)]
+struct LengthCache(AtomicU32);
+
+impl Default for LengthCache
 {
-  path: Vec<GeoCoordinate>
+  fn default() -> Self { Self(AtomicU32::new(f32::NAN.to_bits())) }
 }
 
-impl Default for GeoPath
+impl Clone for LengthCache
 {
-  fn default() -> Self
+  fn clone(&self) -> Self { Self(AtomicU32::new(self.0.load(Ordering::Relaxed))) }
+}
+
+impl LengthCache
+{
+  fn get(&self) -> Option<f32>
+  {
+    let length = f32::from_bits(self.0.load(Ordering::Relaxed));
+    if length.is_nan() { None } else { Some(length) }
+  }
+
+  fn set(&self, length: Option<f32>)
   {
-    Self { path: Vec::new() }
+    self.0.store(length.unwrap_or(f32::NAN).to_bits(), Ordering::Relaxed)
   }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct GeoPath
+{
+  path: Vec<GeoCoordinate>,
+  length_cache: LengthCache
+}
+
 impl Display for GeoPath
 {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
@@ -34,17 +69,63 @@ impl Display for GeoPath
   }
 }
 
+/// Unchecked access to path points, panicking on out-of-bounds like `Vec`.
+/// `IndexMut` is deliberately not implemented: use `replace` so that written coordinates are validated.
+impl Index<usize> for GeoPath
+{
+  type Output = GeoCoordinate;
+
+  fn index(&self, index: usize) -> &Self::Output
+  {
+    &self.path[index]
+  }
+}
+
+impl IntoIterator for GeoPath
+{
+  type Item = GeoCoordinate;
+  type IntoIter = std::vec::IntoIter<GeoCoordinate>;
+
+  fn into_iter(self) -> Self::IntoIter
+  {
+    self.path.into_iter()
+  }
+}
+
+/// Keeps every coordinate as-is, like `new`; invalid points are not filtered out.
+impl FromIterator<GeoCoordinate> for GeoPath
+{
+  fn from_iter<T: IntoIterator<Item = GeoCoordinate>>(iter: T) -> Self
+  {
+    Self { path: iter.into_iter().collect(), length_cache: LengthCache::default() }
+  }
+}
+
 impl GeoPath
 {
   pub fn new(path: &Vec<GeoCoordinate>) -> Self
   {
-    Self { path: path.clone() }
+    Self { path: path.clone(), length_cache: LengthCache::default() }
+  }
+
+  pub fn try_new(path: &[GeoCoordinate]) -> Result<Self, PositioningError>
+  {
+    if let Some(invalid) = path.iter().find(|c| !c.valid()) {
+      return Err(PositioningError::InvalidCoordinate(*invalid))
+    }
+    Ok(Self { path: path.to_vec(), length_cache: LengthCache::default() })
+  }
+
+  pub fn is_valid(&self) -> bool
+  {
+    self.path.iter().all(|c| c.valid())
   }
 
   pub fn add(&mut self, coordinate: GeoCoordinate) -> Result<(), PositioningError>
   {
     if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(coordinate)) }
     self.path.push(coordinate);
+    self.mark_dirty();
     Ok(())
   }
 
@@ -52,6 +133,7 @@ impl GeoPath
   {
     if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(coordinate)) }
     self.path.insert(index, coordinate);
+    self.mark_dirty();
     Ok(())
   }
 
@@ -69,6 +151,7 @@ impl GeoPath
       return Err(PositioningError::IndexOutOfBounds(index, self.path.len()))
     }
     self.path.remove(index);
+    self.mark_dirty();
     Ok(())
   }
 
@@ -79,9 +162,51 @@ impl GeoPath
       return Err(PositioningError::IndexOutOfBounds(index, self.path.len()))
     }
     self.path[index] = coordinate;
+    self.mark_dirty();
     Ok(())
   }
 
+  pub fn split_at(&self, index: usize) -> Result<(GeoPath, GeoPath), PositioningError>
+  {
+    if index >= self.size() { return Err(PositioningError::IndexOutOfBounds(index, self.size())) }
+    Ok((GeoPath::new(&self.path[..=index].to_vec()), GeoPath::new(&self.path[index..].to_vec())))
+  }
+
+  /// Copy of the vertices in `from..to`.
+  pub fn sub_path(&self, from: usize, to: usize) -> Result<GeoPath, PositioningError>
+  {
+    if to > self.size() { return Err(PositioningError::IndexOutOfBounds(to, self.size())) }
+    if from > to { return Err(PositioningError::IndexOutOfBounds(from, to)) }
+    Ok(GeoPath::new(&self.path[from..to].to_vec()))
+  }
+
+  pub fn reverse_segment(&mut self, from: usize, to: usize) -> Result<(), PositioningError>
+  {
+    if to >= self.size() { return Err(PositioningError::IndexOutOfBounds(to, self.size())) }
+    if from > to { return Err(PositioningError::IndexOutOfBounds(from, to)) }
+    self.path[from..=to].reverse();
+    self.mark_dirty();
+    Ok(())
+  }
+
+  pub fn reverse(&mut self)
+  {
+    self.path.reverse()
+  }
+
+  pub fn reversed(&self) -> GeoPath
+  {
+    let mut path = self.clone();
+    path.reverse();
+    path
+  }
+
+  pub fn append(&mut self, other: &GeoPath)
+  {
+    self.path.extend_from_slice(&other.path);
+    self.mark_dirty()
+  }
+
   pub fn contains(&self, coordinate: GeoCoordinate) -> bool
   {
     self.path.contains(&coordinate)
@@ -89,7 +214,22 @@ impl GeoPath
 
   pub fn clear(&mut self)
   {
-    self.path.clear()
+    self.path.clear();
+    self.mark_dirty()
+  }
+
+  pub fn remove_duplicates(&mut self, tolerance_meters: f32)
+  {
+    let before = self.path.len();
+    self.path.dedup_by(|current, kept| matches!(current.distance_to(kept), Ok(d) if d <= tolerance_meters));
+    if self.path.len() != before { self.mark_dirty() }
+  }
+
+  pub fn without_duplicates(&self, tolerance_meters: f32) -> GeoPath
+  {
+    let mut ret = self.clone();
+    ret.remove_duplicates(tolerance_meters);
+    ret
   }
 
   pub fn path(&self) -> &Vec<GeoCoordinate>
@@ -99,7 +239,18 @@ impl GeoPath
 
   pub fn set_path(&mut self, path: Vec<GeoCoordinate>)
   {
-    self.path = path
+    self.path = path;
+    self.mark_dirty()
+  }
+
+  pub fn iter(&self) -> std::slice::Iter<'_, GeoCoordinate>
+  {
+    self.path.iter()
+  }
+
+  pub fn windows(&self) -> impl Iterator<Item = (&GeoCoordinate, &GeoCoordinate)>
+  {
+    self.path.windows(2).map(|w| (&w[0], &w[1]))
   }
 
   pub fn size(&self) -> usize
@@ -108,20 +259,389 @@ impl GeoPath
   }
 
   pub fn length(&self, from: usize, to: usize, length_type: GeoPathLengthType) -> Result<f32, PositioningError>
+  {
+    self.length_with_mode(from, to, length_type, GeoPathDistanceMode::GreatCircle)
+  }
+
+  pub fn length_with_mode(&self, from: usize, to: usize, length_type: GeoPathLengthType, mode: GeoPathDistanceMode)
+                          -> Result<f32, PositioningError>
   {
     if self.path.is_empty() { return Ok(0.0) }
-    let len: f32 = (from..to.clamp(0, self.size() - 1))
-      .map(|i| self.path[i].distance_to(&self.path[i + 1])
-        .expect("Distance calculation failed")
-      ).sum();
+    let distance = |a: &GeoCoordinate, b: &GeoCoordinate| match mode {
+      GeoPathDistanceMode::GreatCircle => a.distance_to(b),
+      GeoPathDistanceMode::RhumbLine => a.rhumb_distance_to(b)
+    };
+    let len = (from..to.clamp(0, self.size() - 1))
+      .map(|i| distance(&self.path[i], &self.path[i + 1]))
+      .sum::<Result<f32, PositioningError>>()?;
     return match length_type {
       GeoPathLengthType::NoLoop => Ok(len),
-      GeoPathLengthType::ClosedLoop => Ok(len + self.path
-        .last()
-        .unwrap()
-        .distance_to(&self.path[from])?
-      )
+      GeoPathLengthType::ClosedLoop => Ok(len + distance(self.path.last().unwrap(), &self.path[from])?)
+    }
+  }
+
+  pub fn close_loop(&mut self)
+  {
+    if let (Some(first), Some(last)) = (self.path.first(), self.path.last()) {
+      if first != last {
+        self.path.push(*first);
+        self.mark_dirty()
+      }
+    }
+  }
+
+  pub fn is_closed(&self, tolerance_meters: f32) -> bool
+  {
+    if self.path.len() < 2 { return false }
+    matches!(self.path[0].distance_to(&self.path[self.path.len() - 1]), Ok(d) if d <= tolerance_meters)
+  }
+
+  pub fn close(&mut self) -> Result<(), PositioningError>
+  {
+    let Some(first) = self.path.first() else { return Ok(()) };
+    if !first.valid() { return Err(PositioningError::InvalidCoordinateAt(0, *first)) }
+    self.close_loop();
+    Ok(())
+  }
+
+  pub fn closed_length(&self) -> Result<f32, PositioningError>
+  {
+    self.length(0, self.size(), GeoPathLengthType::ClosedLoop)
+  }
+
+  pub fn two_opt_improve(&self) -> Result<GeoPath, PositioningError>
+  {
+    const MAX_PASSES: usize = 100;
+
+    let n = self.size();
+    let distances = self.path
+      .iter()
+      .map(|a| self.path
+        .iter()
+        .map(|b| a.distance_to(b))
+        .collect::<Result<Vec<f32>, PositioningError>>()
+      ).collect::<Result<Vec<_>, PositioningError>>()?;
+    let mut order: Vec<usize> = (0..n).collect();
+    for _ in 0..MAX_PASSES {
+      let mut improved = false;
+      for i in 0..n.saturating_sub(2) {
+        for j in i + 2..n {
+          if i == 0 && j == n - 1 { continue }
+          let (a, b) = (order[i], order[i + 1]);
+          let (c, d) = (order[j], order[(j + 1) % n]);
+          let delta = distances[a][c] + distances[b][d] - distances[a][b] - distances[c][d];
+          if delta < -1e-3 {
+            order[i + 1..=j].reverse();
+            improved = true;
+          }
+        }
+      }
+      if !improved { break }
+    }
+    Ok(GeoPath::new(&order.iter().map(|&i| self.path[i]).collect()))
+  }
+
+  pub fn resample_by_latitude(&self, lat_step: f64) -> Result<GeoPath, PositioningError>
+  {
+    if lat_step.is_nan() || lat_step <= 0.0 { return Err(PositioningError::InvalidArgument("latitude step must be positive")) }
+    if let Some(invalid) = self.path.iter().find(|c| !c.valid()) {
+      return Err(PositioningError::InvalidCoordinate(*invalid))
+    }
+    let northbound = self.path.windows(2).all(|w| w[0].latitude <= w[1].latitude);
+    let southbound = self.path.windows(2).all(|w| w[0].latitude >= w[1].latitude);
+    if !northbound && !southbound {
+      return Err(PositioningError::InvalidArgument("path is not monotonic in latitude"))
+    }
+
+    let mut samples = Vec::new();
+    for (a, b) in self.windows() {
+      if a.latitude == b.latitude { continue }
+      let d_lon = normalize_longitude(b.longitude - a.longitude);
+      let mut k = if northbound { (a.latitude / lat_step).ceil() } else { (a.latitude / lat_step).floor() };
+      loop {
+        let lat = k * lat_step;
+        if (northbound && lat >= b.latitude) || (!northbound && lat <= b.latitude) { break }
+        let t = (lat - a.latitude) / (b.latitude - a.latitude);
+        let lon = a.longitude + d_lon * t;
+        samples.push(GeoCoordinate::new(
+          lat,
+          normalize_longitude(lon),
+          a.altitude.zip(b.altitude).map(|(x, y)| x + (y - x) * t as f32)
+        ));
+        k += if northbound { 1.0 } else { -1.0 };
+      }
+    }
+    if let Some(last) = self.path.last() {
+      let k = (last.latitude / lat_step).round();
+      if (k * lat_step - last.latitude).abs() < 1e-9 { samples.push(*last) }
+    }
+    Ok(GeoPath::new(&samples))
+  }
+
+  pub fn insert_graticule_crossings(&self, lat_step: f64, lon_step: f64) -> Result<GeoPath, PositioningError>
+  {
+    if lat_step.is_nan() || lat_step <= 0.0 { return Err(PositioningError::InvalidArgument("latitude step must be positive")) }
+    if lon_step.is_nan() || lon_step <= 0.0 { return Err(PositioningError::InvalidArgument("longitude step must be positive")) }
+    for (i, c) in self.path.iter().enumerate() {
+      if !c.valid() { return Err(PositioningError::InvalidCoordinateAt(i, *c)) }
+    }
+    let crossings = |from: f64, to: f64, step: f64| {
+      let mut fractions = Vec::new();
+      if from == to { return fractions }
+      let mut k = (from.min(to) / step).floor() + 1.0;
+      while k * step < from.max(to) {
+        fractions.push((k * step - from) / (to - from));
+        k += 1.0;
+      }
+      fractions
+    };
+
+    let mut ret = Vec::with_capacity(self.path.len());
+    for (a, b) in self.windows() {
+      ret.push(*a);
+      let d_lon = normalize_longitude(b.longitude - a.longitude);
+      let mut fractions = crossings(a.latitude, b.latitude, lat_step);
+      fractions.extend(crossings(a.longitude, a.longitude + d_lon, lon_step));
+      fractions.sort_by(f64::total_cmp);
+      fractions.dedup_by(|x, y| (*x - *y).abs() < 1e-12);
+      for t in fractions {
+        ret.push(GeoCoordinate::new(
+          a.latitude + (b.latitude - a.latitude) * t,
+          normalize_longitude(a.longitude + d_lon * t),
+          a.altitude.zip(b.altitude).map(|(x, y)| x + (y - x) * t as f32)
+        ));
+      }
+    }
+    if let Some(last) = self.path.last() { ret.push(*last) }
+    Ok(GeoPath::new(&ret))
+  }
+
+  pub fn split_at_antimeridian(&self) -> Vec<GeoPath>
+  {
+    let mut parts = Vec::new();
+    let mut current: Vec<GeoCoordinate> = self.path.first().into_iter().copied().collect();
+    for (a, b) in self.windows() {
+      let d_lon = normalize_longitude(b.longitude - a.longitude);
+      let unwrapped = a.longitude + d_lon;
+      if !(-180.0..=180.0).contains(&unwrapped) {
+        let boundary = if d_lon > 0.0 { 180.0 } else { -180.0 };
+        let t = (boundary - a.longitude) / d_lon;
+        let latitude = a.latitude + (b.latitude - a.latitude) * t;
+        let altitude = a.altitude.zip(b.altitude).map(|(x, y)| x + (y - x) * t as f32);
+        current.push(GeoCoordinate::new(latitude, boundary, altitude));
+        parts.push(GeoPath::new(&current));
+        current = vec![GeoCoordinate::new(latitude, -boundary, altitude)];
+      }
+      current.push(*b);
+    }
+    if !current.is_empty() { parts.push(GeoPath::new(&current)) }
+    parts
+  }
+
+  pub fn to_geojson_multilinestring(&self) -> String
+  {
+    let parts: Vec<String> = self
+      .split_at_antimeridian()
+      .iter()
+      .map(|part| {
+        let positions: Vec<String> = part.iter().map(|c| match c.altitude {
+          Some(altitude) => format!("[{},{},{}]", c.longitude, c.latitude, altitude),
+          None => format!("[{},{}]", c.longitude, c.latitude)
+        }).collect();
+        format!("[{}]", positions.join(","))
+      }).collect();
+    format!("{{\"type\":\"MultiLineString\",\"coordinates\":[{}]}}", parts.join(","))
+  }
+
+  /// KML `<LineString>` with `lon,lat[,alt]` tuples. KML orders longitude first, unlike `GeoCoordinate`.
+  pub fn to_kml(&self) -> String
+  {
+    let tuples: Vec<String> = self.path.iter().map(|c| match c.altitude {
+      Some(altitude) => format!("{},{},{}", c.longitude, c.latitude, altitude),
+      None => format!("{},{}", c.longitude, c.latitude)
+    }).collect();
+    format!("<LineString><coordinates>{}</coordinates></LineString>", tuples.join(" "))
+  }
+
+  pub fn from_kml(kml: &str) -> Result<GeoPath, PositioningError>
+  {
+    let error = |reason: &str| PositioningError::ParseError(reason.to_string());
+    let (_, body) = kml.split_once("<coordinates>").ok_or_else(|| error("missing <coordinates> element"))?;
+    let (body, _) = body.split_once("</coordinates>").ok_or_else(|| error("unterminated <coordinates> element"))?;
+    let mut path = Vec::new();
+    for tuple in body.split_whitespace() {
+      let values = tuple
+        .split(',')
+        .map(|v| v.parse::<f64>().map_err(|_| PositioningError::ParseError(format!("invalid KML tuple: {}", tuple))))
+        .collect::<Result<Vec<f64>, PositioningError>>()?;
+      path.push(match values[..] {
+        [longitude, latitude] => GeoCoordinate::new(latitude, longitude, None),
+        [longitude, latitude, altitude] => GeoCoordinate::new(latitude, longitude, Some(altitude as f32)),
+        _ => return Err(PositioningError::ParseError(format!("invalid KML tuple: {}", tuple)))
+      });
+    }
+    GeoPath::try_new(&path)
+  }
+
+  pub fn project_all(&self, projection: &impl Projection) -> Result<Vec<(f64, f64)>, PositioningError>
+  {
+    self.path
+      .iter()
+      .map(|c| projection.project(c))
+      .collect()
+  }
+
+  /// Keeps only the vertices inside `rect`. Unlike `GeoRectangle::clip_path`, no boundary
+  /// intersection points are inserted and segments leaving the box are simply dropped.
+  pub fn crop_to(&self, rect: &GeoRectangle) -> Result<GeoPath, PositioningError>
+  {
+    let path = self.points_within(rect)?.into_iter().map(|i| self.path[i]).collect();
+    Ok(GeoPath::new(&path))
+  }
+
+  /// Indices of the vertices contained in `rect`.
+  pub fn points_within(&self, rect: &GeoRectangle) -> Result<Vec<usize>, PositioningError>
+  {
+    let mut indices = Vec::new();
+    for (i, coordinate) in self.path.iter().enumerate() {
+      if rect.contains(coordinate)? { indices.push(i) }
+    }
+    Ok(indices)
+  }
+
+  pub fn cumulative_lengths(&self) -> Result<Vec<f32>, PositioningError>
+  {
+    let mut lengths = Vec::with_capacity(self.size());
+    let mut total = 0.0;
+    for (i, coordinate) in self.path.iter().enumerate() {
+      if i > 0 { total += self.path[i - 1].distance_to(coordinate)? }
+      lengths.push(total);
+    }
+    Ok(lengths)
+  }
+
+  pub fn coordinate_at_distance(&self, distance_meters: f32) -> Result<GeoCoordinate, PositioningError>
+  {
+    let lengths = self.cumulative_lengths()?;
+    let (Some(first), Some(last)) = (self.path.first(), self.path.last()) else {
+      return Err(PositioningError::IndexOutOfBounds(0, 0))
+    };
+    if !distance_meters.is_finite() { return Err(PositioningError::InvalidArgument("distance must be finite")) }
+    if distance_meters <= 0.0 { return Ok(*first) }
+    if distance_meters >= *lengths.last().unwrap() { return Ok(*last) }
+    let i = lengths.partition_point(|&length| length <= distance_meters) - 1;
+    let fraction = (distance_meters - lengths[i]) / (lengths[i + 1] - lengths[i]);
+    self.path[i].interpolate(&self.path[i + 1], fraction as f64)
+  }
+
+  pub fn split_equal(&self, parts: usize) -> Result<Vec<GeoPath>, PositioningError>
+  {
+    if parts == 0 { return Err(PositioningError::InvalidArgument("number of parts must be positive")) }
+    if self.size() < 2 { return Err(PositioningError::NotEnoughPoints { needed: 2, got: self.size() }) }
+    let lengths = self.cumulative_lengths()?;
+    let total = *lengths.last().unwrap();
+    let mut ret = Vec::with_capacity(parts);
+    let mut start = self.path[0];
+    for k in 1..=parts {
+      let (from, to) = (total * (k - 1) as f32 / parts as f32, total * k as f32 / parts as f32);
+      let end = if k == parts { *self.path.last().unwrap() } else { self.coordinate_at_distance(to)? };
+      let mut part = vec![start];
+      part.extend(self.path
+        .iter()
+        .zip(&lengths)
+        .filter(|(_, &length)| length > from && length < to)
+        .map(|(c, _)| *c));
+      part.push(end);
+      ret.push(GeoPath::new(&part));
+      start = end;
+    }
+    Ok(ret)
+  }
+
+  pub fn segment_azimuths(&self) -> Result<Vec<f32>, PositioningError>
+  {
+    self.windows().map(|(a, b)| a.azimuth_to(b)).collect()
+  }
+
+  pub fn closest_nonadjacent_vertices(&self) -> Result<Option<(usize, usize, f32)>, PositioningError>
+  {
+    let mut best: Option<(usize, usize, f32)> = None;
+    for i in 0..self.path.len().saturating_sub(2) {
+      let distances = self.path[i].distances_to_many(&self.path[i + 2..])?;
+      for (k, distance) in distances.into_iter().enumerate() {
+        if !matches!(best, Some((_, _, d)) if d <= distance) { best = Some((i, i + 2 + k, distance)) }
+      }
+    }
+    Ok(best)
+  }
+
+  pub fn mean_bearing(&self) -> Result<f32, PositioningError>
+  {
+    if self.size() < 2 { return Err(PositioningError::NotEnoughPoints { needed: 2, got: self.size() }) }
+    let (mut x, mut y) = (0.0f64, 0.0f64);
+    for (a, b) in self.windows() {
+      let (bearing, distance) = a.bearing_and_distance_to(b)?;
+      x += distance as f64 * (bearing as f64).to_radians().cos();
+      y += distance as f64 * (bearing as f64).to_radians().sin();
+    }
+    if x.hypot(y) < 1e-9 { return Err(PositioningError::InvalidArgument("path has no dominant direction")) }
+    Ok(y.atan2(x).to_degrees().rem_euclid(360.0) as f32)
+  }
+
+  /// Douglas-Peucker simplification with the tolerance binary-searched so that at most `max_points`
+  /// vertices remain. The endpoints are always kept, so the result has at least two points.
+  pub fn simplify_to_count(&self, max_points: usize) -> GeoPath
+  {
+    const ITERATIONS: usize = 50;
+
+    if self.size() <= max_points.max(2) { return self.clone() }
+    let (mut low, mut high) = (0.0, self.douglas_peucker_max_deviation());
+    let mut best = vec![0, self.size() - 1];
+    for _ in 0..ITERATIONS {
+      let tolerance = (low + high) / 2.0;
+      let kept = self.douglas_peucker(tolerance);
+      if kept.len() <= max_points { (high, best) = (tolerance, kept) } else { low = tolerance }
+    }
+    GeoPath::new(&best.into_iter().map(|i| self.path[i]).collect())
+  }
+
+  pub fn elevation_profile(&self) -> Result<Vec<(f32, Option<f32>)>, PositioningError>
+  {
+    Ok(self
+      .cumulative_lengths()?
+      .into_iter()
+      .zip(self.path.iter().map(|c| c.altitude))
+      .collect())
+  }
+
+  /// Treats the path as a ring (the closing segment is implied) and checks that it has at least three
+  /// distinct points and no two non-adjacent edges touch or cross. Edges are tested in the lat/lon plane.
+  pub fn is_simple(&self) -> Result<bool, PositioningError>
+  {
+    let mut ring = self.path.clone();
+    for (i, c) in ring.iter().enumerate() {
+      if !c.valid() { return Err(PositioningError::InvalidCoordinateAt(i, *c)) }
+    }
+    ring.dedup();
+    while ring.len() > 1 && ring.first() == ring.last() { ring.pop(); }
+    if ring.len() < 3 { return Ok(false) }
+
+    let mut points: Vec<(f64, f64)> = Vec::with_capacity(ring.len());
+    for c in &ring {
+      let x = match points.last() {
+        Some(&(prev, _)) => prev + normalize_longitude(c.longitude - prev),
+        None => c.longitude
+      };
+      points.push((x, c.latitude));
     }
+    let n = points.len();
+    for i in 0..n {
+      for j in (i + 2)..n {
+        if i == 0 && j == n - 1 { continue }
+        if segments_intersect(points[i], points[i + 1], points[j], points[(j + 1) % n]) { return Ok(false) }
+      }
+    }
+    Ok(true)
   }
 
   pub fn bounding_georectangle(&self)// -> GeoRectangle
@@ -139,8 +659,647 @@ impl GeoPath
     todo!("Implement GeoPath::translated()")
   }
 
+  pub fn total_length(&self) -> Result<f32, PositioningError>
+  {
+    if let Some(length) = self.length_cache.get() { return Ok(length) }
+    let length = self.cumulative_lengths()?.last().copied().unwrap_or(0.0);
+    self.length_cache.set(Some(length));
+    Ok(length)
+  }
+
+  pub fn is_length_cached(&self) -> bool
+  {
+    self.length_cache.get().is_some()
+  }
+
+  pub fn recompute_length(&mut self) -> Result<f32, PositioningError>
+  {
+    self.mark_dirty();
+    self.total_length()
+  }
+
+  fn douglas_peucker(&self, tolerance_meters: f64) -> Vec<usize>
+  {
+    let mut keep = vec![false; self.size()];
+    let (first, last) = (0, self.size() - 1);
+    keep[first] = true;
+    keep[last] = true;
+    let mut stack = vec![(first, last)];
+    while let Some((start, end)) = stack.pop() {
+      if end <= start + 1 { continue }
+      let (index, deviation) = (start + 1..end)
+        .map(|i| (i, Self::segment_deviation(&self.path[i], &self.path[start], &self.path[end])))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap();
+      if deviation > tolerance_meters {
+        keep[index] = true;
+        stack.push((start, index));
+        stack.push((index, end));
+      }
+    }
+    keep.iter().enumerate().filter(|(_, &k)| k).map(|(i, _)| i).collect()
+  }
+
+  fn douglas_peucker_max_deviation(&self) -> f64
+  {
+    let (first, last) = (&self.path[0], &self.path[self.size() - 1]);
+    self.path.iter().map(|c| Self::segment_deviation(c, first, last)).fold(0.0, f64::max) + 1.0
+  }
+
+  /// Distance in meters from `point` to the segment `a`-`b` in a local equirectangular projection around `a`.
+  fn segment_deviation(point: &GeoCoordinate, a: &GeoCoordinate, b: &GeoCoordinate) -> f64
+  {
+    let radius = Constants::EARTH_MEAN_RADIUS as f64;
+    let scale = a.latitude.to_radians().cos();
+    let project = |c: &GeoCoordinate| (
+      normalize_longitude(c.longitude - a.longitude).to_radians() * scale * radius,
+      (c.latitude - a.latitude).to_radians() * radius
+    );
+    let ((px, py), (bx, by)) = (project(point), project(b));
+    let length_sq = bx * bx + by * by;
+    let t = if length_sq > 0.0 { ((px * bx + py * by) / length_sq).clamp(0.0, 1.0) } else { 0.0 };
+    (px - t * bx).hypot(py - t * by)
+  }
+
   fn mark_dirty(&mut self)
   {
-    todo!("Implement GeoPath::mark_dirty()")
+    self.length_cache.set(None)
+  }
+}
+
+fn segments_intersect(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64)) -> bool
+{
+  let orientation = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| {
+    let cross = (q.0 - p.0) * (r.1 - p.1) - (q.1 - p.1) * (r.0 - p.0);
+    if cross > 0.0 { 1 } else if cross < 0.0 { -1 } else { 0 }
+  };
+  let on_segment = |p: (f64, f64), q: (f64, f64), r: (f64, f64)| {
+    r.0 >= p.0.min(q.0) && r.0 <= p.0.max(q.0) && r.1 >= p.1.min(q.1) && r.1 <= p.1.max(q.1)
+  };
+  let (o1, o2, o3, o4) = (orientation(a, b, c), orientation(a, b, d), orientation(c, d, a), orientation(c, d, b));
+  if o1 != o2 && o3 != o4 { return true }
+  (o1 == 0 && on_segment(a, b, c)) || (o2 == 0 && on_segment(a, b, d))
+    || (o3 == 0 && on_segment(c, d, a)) || (o4 == 0 && on_segment(c, d, b))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_path() -> GeoPath
+  {
+    GeoPath::new(&vec![
+      GeoCoordinate::new(60.0, 30.0, None),
+      GeoCoordinate::new(60.0, 31.0, None),
+      GeoCoordinate::new(59.0, 31.0, None)
+    ])
+  }
+
+  #[test]
+  fn test_reverse()
+  {
+    let path = test_path();
+    let reversed = path.reversed();
+    assert_eq!(reversed.at(0).unwrap(), path.at(2).unwrap());
+    assert_eq!(reversed.at(2).unwrap(), path.at(0).unwrap());
+    assert_eq!(
+      reversed.length(0, reversed.size(), GeoPathLengthType::NoLoop).unwrap().round(),
+      path.length(0, path.size(), GeoPathLengthType::NoLoop).unwrap().round()
+    );
+  }
+
+  #[test]
+  fn test_append()
+  {
+    let mut path = test_path();
+    let other = test_path().reversed();
+    path.append(&other);
+    assert_eq!(path.size(), 6);
+    assert_eq!(path.at(3).unwrap(), other.at(0).unwrap());
+  }
+
+  #[test]
+  fn test_iter()
+  {
+    let path = test_path();
+    assert_eq!(path.iter().count(), 3);
+    assert_eq!(path.iter().next(), Some(&GeoCoordinate::new(60.0, 30.0, None)));
+    let pairs: Vec<(&GeoCoordinate, &GeoCoordinate)> = path.windows().collect();
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[1], (&GeoCoordinate::new(60.0, 31.0, None), &GeoCoordinate::new(59.0, 31.0, None)));
+    let coordinates: Vec<GeoCoordinate> = path.clone().into_iter().collect();
+    assert_eq!(&coordinates, path.path());
+  }
+
+  #[test]
+  fn test_close_loop()
+  {
+    let mut path = test_path();
+    let open = path.length(0, path.size(), GeoPathLengthType::NoLoop).unwrap();
+    let closing = path.at(2).unwrap().distance_to(&path.at(0).unwrap()).unwrap();
+    assert_eq!(path.closed_length().unwrap(), open + closing);
+
+    path.close_loop();
+    assert_eq!(path.size(), 4);
+    assert_eq!(path.at(3).unwrap(), path.at(0).unwrap());
+    path.close_loop();
+    assert_eq!(path.size(), 4);
+  }
+
+  #[test]
+  fn test_index()
+  {
+    let path = test_path();
+    assert_eq!(path[1], GeoCoordinate::new(60.0, 31.0, None));
+    assert_eq!(path[1], path.at(1).unwrap());
+  }
+
+  #[test]
+  #[should_panic]
+  fn test_index_out_of_bounds()
+  {
+    let path = test_path();
+    let _ = path[3];
+  }
+
+  #[test]
+  fn test_two_opt_improve()
+  {
+    let crossed = GeoPath::new(&vec![
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(1.0, 1.0, None),
+      GeoCoordinate::new(1.0, 0.0, None),
+      GeoCoordinate::new(0.0, 1.0, None)
+    ]);
+    let improved = crossed.two_opt_improve().unwrap();
+    assert_eq!(improved.size(), crossed.size());
+    assert_eq!(improved.at(0).unwrap(), crossed.at(0).unwrap());
+    assert!(improved.closed_length().unwrap() < crossed.closed_length().unwrap());
+    assert_eq!(
+      improved.two_opt_improve().unwrap().closed_length().unwrap(),
+      improved.closed_length().unwrap()
+    );
+  }
+
+  #[test]
+  fn test_resample_by_latitude()
+  {
+    let path = GeoPath::new(&vec![
+      GeoCoordinate::new(59.5, 30.0, Some(0.0)),
+      GeoCoordinate::new(61.5, 32.0, Some(200.0)),
+      GeoCoordinate::new(63.0, 32.0, Some(200.0))
+    ]);
+    let samples = path.resample_by_latitude(1.0).unwrap();
+    assert_eq!(samples.path(), &vec![
+      GeoCoordinate::new(60.0, 30.5, Some(50.0)),
+      GeoCoordinate::new(61.0, 31.5, Some(150.0)),
+      GeoCoordinate::new(62.0, 32.0, Some(200.0)),
+      GeoCoordinate::new(63.0, 32.0, Some(200.0))
+    ]);
+    assert_eq!(samples.reversed().path(), path.reversed().resample_by_latitude(1.0).unwrap().path());
+    assert!(path.resample_by_latitude(0.0).is_err());
+    assert!(test_path().resample_by_latitude(1.0).is_ok());
+    let zigzag = GeoPath::new(&vec![
+      GeoCoordinate::new(59.0, 30.0, None),
+      GeoCoordinate::new(61.0, 30.0, None),
+      GeoCoordinate::new(60.0, 30.0, None)
+    ]);
+    assert!(matches!(zigzag.resample_by_latitude(1.0), Err(PositioningError::InvalidArgument(_))));
+  }
+
+  #[test]
+  fn test_length_cache()
+  {
+    let mut path = test_path();
+    assert!(!path.is_length_cached());
+    let length = path.total_length().unwrap();
+    assert!(path.is_length_cached());
+    assert_eq!(length, path.length(0, path.size(), GeoPathLengthType::NoLoop).unwrap());
+
+    path.add(GeoCoordinate::new(59.0, 30.0, None)).unwrap();
+    assert!(!path.is_length_cached());
+    let recomputed = path.recompute_length().unwrap();
+    assert!(path.is_length_cached());
+    assert!(recomputed > length);
+    assert_eq!(path.total_length().unwrap(), recomputed);
+  }
+
+  #[test]
+  fn test_crop_to()
+  {
+    let rect = GeoRectangle::new(
+      GeoCoordinate::new(60.5, 29.5, None),
+      GeoCoordinate::new(59.5, 31.5, None)
+    );
+    let cropped = test_path().crop_to(&rect).unwrap();
+    assert_eq!(cropped.path(), &vec![GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(60.0, 31.0, None)]);
+    assert!(test_path().crop_to(&GeoRectangle::default()).is_err());
+  }
+
+  #[test]
+  fn test_cumulative_lengths()
+  {
+    let path = test_path();
+    let lengths = path.cumulative_lengths().unwrap();
+    assert_eq!(lengths.len(), path.size());
+    assert_eq!(lengths[0], 0.0);
+    assert!(lengths.windows(2).all(|w| w[0] <= w[1]));
+    assert_eq!(*lengths.last().unwrap(), path.length(0, path.size(), GeoPathLengthType::NoLoop).unwrap());
+    assert!(GeoPath::default().cumulative_lengths().unwrap().is_empty());
+    let invalid = GeoPath::new(&vec![GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::default()]);
+    assert!(invalid.cumulative_lengths().is_err());
+  }
+
+  #[test]
+  fn test_coordinate_at_distance()
+  {
+    let path = test_path();
+    let total = path.length(0, path.size(), GeoPathLengthType::NoLoop).unwrap();
+    assert_eq!(path.coordinate_at_distance(0.0).unwrap(), path[0]);
+    assert_eq!(path.coordinate_at_distance(-10.0).unwrap(), path[0]);
+    assert_eq!(path.coordinate_at_distance(total).unwrap(), path[2]);
+    assert_eq!(path.coordinate_at_distance(total + 10.0).unwrap(), path[2]);
+
+    let first_leg = path[0].distance_to(&path[1]).unwrap();
+    assert_eq!(path.coordinate_at_distance(first_leg).unwrap(), path[1]);
+    let middle = path.coordinate_at_distance(first_leg / 2.0).unwrap();
+    assert!((middle.distance_to(&path[0]).unwrap() - first_leg / 2.0).abs() < 0.01);
+    assert!((middle.distance_to(&path[1]).unwrap() - first_leg / 2.0).abs() < 0.01);
+    assert!(GeoPath::default().coordinate_at_distance(0.0).is_err());
+    assert_eq!(path.coordinate_at_distance(f32::NAN).unwrap_err(), PositioningError::InvalidArgument("distance must be finite"));
+    assert!(path.coordinate_at_distance(f32::INFINITY).is_err());
+  }
+
+  #[test]
+  fn test_elevation_profile()
+  {
+    let path = GeoPath::new(&vec![
+      GeoCoordinate::new(60.0, 30.0, Some(10.0)),
+      GeoCoordinate::new(60.0, 31.0, None),
+      GeoCoordinate::new(59.0, 31.0, Some(30.0))
+    ]);
+    let profile = path.elevation_profile().unwrap();
+    let lengths = path.cumulative_lengths().unwrap();
+    assert_eq!(profile.iter().map(|p| p.0).collect::<Vec<f32>>(), lengths);
+    assert!(profile.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert_eq!(profile.iter().map(|p| p.1).collect::<Vec<Option<f32>>>(), vec![Some(10.0), None, Some(30.0)]);
+  }
+
+  #[test]
+  fn test_remove_duplicates()
+  {
+    let fix = GeoCoordinate::new(60.0, 30.0, None);
+    let mut t = GeoPath::new(&vec![fix; 5]);
+    t.remove_duplicates(0.0);
+    assert_eq!(t.path(), &vec![fix]);
+
+    let mut t = GeoPath::new(&vec![]);
+    t.remove_duplicates(1.0);
+    assert_eq!(t.size(), 0);
+
+    // 0.00001 deg of latitude is about 1.11 m
+    let t = GeoPath::new(&vec![
+      fix,
+      fix,
+      GeoCoordinate::new(60.00001, 30.0, None),
+      GeoCoordinate::new(60.00002, 30.0, None),
+      GeoCoordinate::new(60.0, 31.0, None)
+    ]);
+    assert_eq!(t.without_duplicates(1.0).size(), 4);
+    assert_eq!(t.without_duplicates(1.2).size(), 3);
+    assert_eq!(t.without_duplicates(1.2).path()[1], GeoCoordinate::new(60.00002, 30.0, None));
+    assert_eq!(t.size(), 5);
+
+    let mut t = test_path();
+    t.total_length().unwrap();
+    t.add(GeoCoordinate::new(59.0, 31.0, None)).unwrap();
+    t.total_length().unwrap();
+    t.remove_duplicates(1.0);
+    assert!(!t.is_length_cached());
+  }
+
+  #[test]
+  fn test_is_simple()
+  {
+    let square = GeoPath::new(&vec![
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(0.0, 1.0, None),
+      GeoCoordinate::new(1.0, 1.0, None),
+      GeoCoordinate::new(1.0, 0.0, None),
+      GeoCoordinate::new(0.0, 0.0, None)
+    ]);
+    assert!(square.is_simple().unwrap());
+    let figure_eight = GeoPath::new(&vec![
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(1.0, 1.0, None),
+      GeoCoordinate::new(1.0, 0.0, None),
+      GeoCoordinate::new(0.0, 1.0, None)
+    ]);
+    assert!(!figure_eight.is_simple().unwrap());
+    let antimeridian = GeoPath::new(&vec![
+      GeoCoordinate::new(0.0, 179.0, None),
+      GeoCoordinate::new(0.0, -179.0, None),
+      GeoCoordinate::new(1.0, -179.0, None),
+      GeoCoordinate::new(1.0, 179.0, None)
+    ]);
+    assert!(antimeridian.is_simple().unwrap());
+    let degenerate = GeoPath::new(&vec![GeoCoordinate::new(0.0, 0.0, None); 4]);
+    assert!(!degenerate.is_simple().unwrap());
+    assert!(GeoPath::new(&vec![GeoCoordinate::default()]).is_simple().is_err());
+  }
+
+  #[test]
+  fn test_reverse_segment()
+  {
+    let points: Vec<GeoCoordinate> = (0..5).map(|i| GeoCoordinate::new(60.0, 30.0 + i as f64, None)).collect();
+    let mut t = GeoPath::new(&points);
+    t.reverse_segment(1, 3).unwrap();
+    assert_eq!(t.path(), &vec![points[0], points[3], points[2], points[1], points[4]]);
+    t.reverse_segment(2, 2).unwrap();
+    assert_eq!(t[2], points[2]);
+    assert_eq!(t.reverse_segment(3, 5), Err(PositioningError::IndexOutOfBounds(5, 5)));
+    assert_eq!(t.reverse_segment(3, 1), Err(PositioningError::IndexOutOfBounds(3, 1)));
+  }
+
+  #[test]
+  fn test_insert_graticule_crossings()
+  {
+    let t = GeoPath::new(&vec![GeoCoordinate::new(60.2, 29.5, None), GeoCoordinate::new(60.4, 30.5, None)]);
+    let crossed = t.insert_graticule_crossings(1.0, 1.0).unwrap();
+    assert_eq!(crossed.size(), 3);
+    assert_eq!(crossed[1], GeoCoordinate::new(60.3, 30.0, None));
+
+    let crossed = test_path().insert_graticule_crossings(0.5, 0.5).unwrap();
+    assert_eq!(crossed.size(), 5);
+    assert_eq!(crossed[1], GeoCoordinate::new(60.0, 30.5, None));
+    assert_eq!(crossed[3], GeoCoordinate::new(59.5, 31.0, None));
+
+    let antimeridian = GeoPath::new(&vec![GeoCoordinate::new(0.0, 179.5, None), GeoCoordinate::new(0.0, -179.5, None)]);
+    let crossed = antimeridian.insert_graticule_crossings(1.0, 1.0).unwrap();
+    assert_eq!(crossed.size(), 3);
+    assert_eq!(crossed[1].longitude.abs(), 180.0);
+    assert!(t.insert_graticule_crossings(0.0, 1.0).is_err());
+  }
+
+  #[test]
+  fn test_close()
+  {
+    let mut t = test_path();
+    assert!(!t.is_closed(1.0));
+    let loop_length = t.length(0, t.size(), GeoPathLengthType::ClosedLoop).unwrap();
+    t.close().unwrap();
+    assert_eq!(t.size(), 4);
+    assert!(t.is_closed(0.0));
+    assert!((t.length(0, t.size(), GeoPathLengthType::NoLoop).unwrap() - loop_length).abs() < 1e-3);
+    t.close().unwrap();
+    assert_eq!(t.size(), 4);
+
+    let mut empty = GeoPath::default();
+    assert_eq!(empty.close(), Ok(()));
+    assert_eq!(empty.size(), 0);
+    assert!(!empty.is_closed(1.0));
+  }
+
+  #[test]
+  fn test_to_geojson_multilinestring()
+  {
+    let t = GeoPath::new(&vec![
+      GeoCoordinate::new(10.0, 170.0, None),
+      GeoCoordinate::new(20.0, -170.0, None),
+      GeoCoordinate::new(20.0, -160.0, None)
+    ]);
+    let parts = t.split_at_antimeridian();
+    assert_eq!(parts.len(), 2);
+    assert_eq!(parts[0].at(1).unwrap(), GeoCoordinate::new(15.0, 180.0, None));
+    assert_eq!(parts[1].at(0).unwrap(), GeoCoordinate::new(15.0, -180.0, None));
+    assert_eq!(t.to_geojson_multilinestring(),
+      r#"{"type":"MultiLineString","coordinates":[[[170,10],[180,15]],[[-180,15],[-170,20],[-160,20]]]}"#);
+    assert_eq!(test_path().to_geojson_multilinestring(),
+      r#"{"type":"MultiLineString","coordinates":[[[30,60],[31,60],[31,59]]]}"#);
+    assert_eq!(GeoPath::default().to_geojson_multilinestring(), r#"{"type":"MultiLineString","coordinates":[]}"#);
+  }
+
+  #[test]
+  fn test_from_iter()
+  {
+    let t: GeoPath = test_path().path().clone().into_iter().collect();
+    assert_eq!(t.size(), 3);
+    assert_eq!(t.path(), test_path().path());
+    let with_invalid = GeoPath::from_iter([GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::default()]);
+    assert_eq!(with_invalid.size(), 2);
+  }
+
+  #[test]
+  fn test_split_equal()
+  {
+    let t = test_path();
+    let total = t.total_length().unwrap();
+    let parts = t.split_equal(3).unwrap();
+    assert_eq!(parts.len(), 3);
+    for part in &parts {
+      assert!((part.total_length().unwrap() - total / 3.0).abs() < 1.0);
+    }
+    assert_eq!(parts[0].at(0).unwrap(), t[0]);
+    assert_eq!(parts[0].at(parts[0].size() - 1).unwrap(), parts[1].at(0).unwrap());
+    assert_eq!(parts[1].at(parts[1].size() - 1).unwrap(), parts[2].at(0).unwrap());
+    assert_eq!(parts[2].at(parts[2].size() - 1).unwrap(), t[2]);
+    assert_eq!(t.split_equal(1).unwrap()[0].path(), t.path());
+    assert!(t.split_equal(0).is_err());
+    assert_eq!(GeoPath::default().split_equal(2).unwrap_err(), PositioningError::NotEnoughPoints { needed: 2, got: 0 });
+  }
+
+  #[test]
+  fn test_mean_bearing()
+  {
+    let start = GeoCoordinate::new(0.0, 0.0, None);
+    let turn = start.at_distance_and_azimuth(10000.0, 350.0).unwrap();
+    let end = turn.at_distance_and_azimuth(10000.0, 10.0).unwrap();
+    let bearing = GeoPath::new(&vec![start, turn, end]).mean_bearing().unwrap();
+    assert!(!(0.1..=359.9).contains(&bearing), "{}", bearing);
+
+    let weighted = GeoPath::new(&vec![
+      start,
+      start.at_distance_and_azimuth(30000.0, 90.0).unwrap(),
+      start.at_distance_and_azimuth(30000.0, 90.0).unwrap().at_distance_and_azimuth(10000.0, 0.0).unwrap()
+    ]).mean_bearing().unwrap();
+    assert!((weighted - 71.565).abs() < 0.1, "{}", weighted);
+    assert!(GeoPath::new(&vec![start]).mean_bearing().is_err());
+    assert!(GeoPath::new(&vec![start, start]).mean_bearing().is_err());
+  }
+
+  #[test]
+  fn test_try_new()
+  {
+    let t = GeoPath::try_new(test_path().path()).unwrap();
+    assert_eq!(t.size(), 3);
+    assert!(t.is_valid());
+    let points = [GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(91.0, 30.0, None), GeoCoordinate::new(59.0, 30.0, None)];
+    assert_eq!(GeoPath::try_new(&points).unwrap_err(), PositioningError::InvalidCoordinate(points[1]));
+    assert!(!GeoPath::new(&points.to_vec()).is_valid());
+    assert!(GeoPath::try_new(&[]).unwrap().is_valid());
+  }
+
+  #[test]
+  fn test_kml()
+  {
+    let t = test_path();
+    assert_eq!(t.to_kml(), "<LineString><coordinates>30,60 31,60 31,59</coordinates></LineString>");
+    assert_eq!(GeoPath::from_kml(&t.to_kml()).unwrap().path(), t.path());
+
+    let elevated = GeoPath::new(&vec![GeoCoordinate::new(60.5, 30.25, Some(12.5)), GeoCoordinate::new(-10.0, -75.0, Some(0.0))]);
+    let parsed = GeoPath::from_kml(&elevated.to_kml()).unwrap();
+    assert_eq!(parsed.path(), elevated.path());
+    assert_eq!(parsed[0].altitude(), Some(12.5));
+
+    let document = "<Placemark><LineString>\n  <coordinates>\n    30,60,5\n    31,60,6\n  </coordinates>\n</LineString></Placemark>";
+    assert_eq!(GeoPath::from_kml(document).unwrap()[1], GeoCoordinate::new(60.0, 31.0, Some(6.0)));
+    for bad in ["", "<coordinates>30,60", "<coordinates>30</coordinates>", "<coordinates>a,b</coordinates>"] {
+      assert!(matches!(GeoPath::from_kml(bad), Err(PositioningError::ParseError(_))), "{}", bad);
+    }
+    assert!(GeoPath::from_kml("<coordinates>30,95</coordinates>").is_err());
+  }
+
+  #[test]
+  fn test_segment_azimuths()
+  {
+    let t = GeoPath::new(&vec![
+      GeoCoordinate::new(0.0, 0.0, None),
+      GeoCoordinate::new(0.0, 1.0, None),
+      GeoCoordinate::new(1.0, 1.0, None)
+    ]);
+    let azimuths = t.segment_azimuths().unwrap();
+    assert_eq!(azimuths.len(), 2);
+    assert!((azimuths[0] - 90.0).abs() < 1e-3);
+    assert!(azimuths[1].abs() < 1e-3);
+    assert!(GeoPath::default().segment_azimuths().unwrap().is_empty());
+    assert!(GeoPath::new(&vec![GeoCoordinate::new(0.0, 0.0, None), GeoCoordinate::default()]).segment_azimuths().is_err());
+  }
+
+  #[test]
+  fn test_closest_nonadjacent_vertices()
+  {
+    let t = GeoPath::new(&vec![
+      GeoCoordinate::new(60.0, 30.0, None),
+      GeoCoordinate::new(60.0, 30.1, None),
+      GeoCoordinate::new(60.1, 30.1, None),
+      GeoCoordinate::new(60.1, 30.2, None),
+      GeoCoordinate::new(60.001, 30.101, None),
+      GeoCoordinate::new(59.9, 30.1, None)
+    ]);
+    let (i, j, distance) = t.closest_nonadjacent_vertices().unwrap().unwrap();
+    assert_eq!((i, j), (1, 4));
+    assert!((distance - t[1].distance_to(&t[4]).unwrap()).abs() < 1e-3);
+    assert_eq!(test_path().closest_nonadjacent_vertices().unwrap().unwrap().0, 0);
+    assert_eq!(GeoPath::new(&vec![t[0], t[1]]).closest_nonadjacent_vertices().unwrap(), None);
+    assert_eq!(GeoPath::default().closest_nonadjacent_vertices().unwrap(), None);
+  }
+
+  #[test]
+  fn test_split_at()
+  {
+    let t = test_path();
+    let (head, tail) = t.split_at(1).unwrap();
+    assert_eq!(head.path(), &vec![t[0], t[1]]);
+    assert_eq!(tail.path(), &vec![t[1], t[2]]);
+    let total = head.length(0, head.size(), GeoPathLengthType::NoLoop).unwrap()
+      + tail.length(0, tail.size(), GeoPathLengthType::NoLoop).unwrap();
+    assert!((total - t.total_length().unwrap()).abs() < 1e-2);
+    assert_eq!(t.split_at(0).unwrap().0.size(), 1);
+    assert_eq!(t.split_at(2).unwrap().1.size(), 1);
+    assert_eq!(t.split_at(3).unwrap_err(), PositioningError::IndexOutOfBounds(3, 3));
+  }
+
+  #[test]
+  fn test_simplify_to_count()
+  {
+    let track: Vec<GeoCoordinate> = (0..1000)
+      .map(|i| {
+        let t = i as f64 / 999.0;
+        GeoCoordinate::new(60.0 + 0.05 * (t * 20.0).sin() + 0.001 * (t * 500.0).cos(), 30.0 + t, None)
+      }).collect();
+    let t = GeoPath::new(&track);
+    let simplified = t.simplify_to_count(10);
+    assert!(simplified.size() <= 10);
+    assert!(simplified.size() >= 5);
+    assert_eq!(simplified[0], track[0]);
+    assert_eq!(simplified[simplified.size() - 1], track[999]);
+    assert_eq!(t.simplify_to_count(1).size(), 2);
+    assert_eq!(test_path().simplify_to_count(10).path(), test_path().path());
+    assert_eq!(test_path().simplify_to_count(2).size(), 2);
+  }
+
+  #[test]
+  fn test_sub_path()
+  {
+    let mut t = test_path();
+    t.add(GeoCoordinate::new(60.1, 30.4, None)).unwrap();
+    let middle = t.sub_path(1, 3).unwrap();
+    assert_eq!(middle.path(), &vec![t[1], t[2]]);
+    assert!(t.sub_path(2, 2).unwrap().path().is_empty());
+    assert_eq!(t.sub_path(0, 4).unwrap().path(), t.path());
+    assert_eq!(t.sub_path(0, 5).unwrap_err(), PositioningError::IndexOutOfBounds(5, 4));
+    assert_eq!(t.sub_path(3, 2).unwrap_err(), PositioningError::IndexOutOfBounds(3, 2));
+  }
+
+  #[test]
+  fn test_length_with_mode()
+  {
+    let t = GeoPath::new(&vec![GeoCoordinate::new(60.0, -20.0, None), GeoCoordinate::new(60.0, 40.0, None)]);
+    let great_circle = t.length_with_mode(0, t.size(), GeoPathLengthType::NoLoop, GeoPathDistanceMode::GreatCircle).unwrap();
+    let rhumb = t.length_with_mode(0, t.size(), GeoPathLengthType::NoLoop, GeoPathDistanceMode::RhumbLine).unwrap();
+    assert_eq!(great_circle, t.length(0, t.size(), GeoPathLengthType::NoLoop).unwrap());
+    assert!(rhumb > great_circle + 50000.0);
+
+    let m = test_path();
+    let closed = m.length_with_mode(0, m.size(), GeoPathLengthType::ClosedLoop, GeoPathDistanceMode::RhumbLine).unwrap();
+    let open = m.length_with_mode(0, m.size(), GeoPathLengthType::NoLoop, GeoPathDistanceMode::RhumbLine).unwrap();
+    assert!(closed > open);
+  }
+
+  #[test]
+  fn test_points_within()
+  {
+    let t = GeoPath::new(&vec![
+      GeoCoordinate::new(55.0, 35.0, None),
+      GeoCoordinate::new(65.0, 35.0, None),
+      GeoCoordinate::new(56.0, 36.0, None),
+      GeoCoordinate::new(55.0, 45.0, None)
+    ]);
+    let rect = GeoRectangle::new(GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::new(50.0, 40.0, None));
+    assert_eq!(t.points_within(&rect).unwrap(), vec![0, 2]);
+    assert!(GeoPath::new(&vec![]).points_within(&rect).unwrap().is_empty());
+    assert!(t.points_within(&GeoRectangle::default()).is_err());
+  }
+
+  #[test]
+  fn test_total_length_invalid()
+  {
+    let mut t = GeoPath::new(&vec![GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::default()]);
+    assert_eq!(t.total_length().unwrap_err(), PositioningError::InvalidCoordinate(GeoCoordinate::default()));
+    assert!(!t.is_length_cached());
+    assert!(t.recompute_length().is_err());
+    assert_eq!(GeoPath::new(&vec![]).total_length().unwrap(), 0.0);
+  }
+
+  #[test]
+  fn test_thread_safe()
+  {
+    fn assert_sync<T: Send + Sync + std::panic::RefUnwindSafe>() {}
+    assert_sync::<GeoPath>();
+    let path = std::sync::Arc::new(test_path());
+    let shared = path.clone();
+    let length = std::thread::spawn(move || shared.total_length().unwrap()).join().unwrap();
+    assert!(path.is_length_cached());
+    assert_eq!(path.total_length().unwrap(), length);
+  }
+
+  #[test]
+  fn test_length_with_mode_invalid()
+  {
+    let t = GeoPath::new(&vec![GeoCoordinate::new(60.0, 30.0, None), GeoCoordinate::default()]);
+    for mode in [GeoPathDistanceMode::GreatCircle, GeoPathDistanceMode::RhumbLine] {
+      assert!(t.length_with_mode(0, t.size(), GeoPathLengthType::NoLoop, mode).is_err());
+    }
+    assert!(t.length(0, t.size(), GeoPathLengthType::NoLoop).is_err());
   }
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use std::f64::consts::FRAC_PI_4;
+use crate::positioning::constants as Constants;
+use crate::positioning::errors::PositioningError;
+use crate::positioning::GeoCoordinate;
+
+const PROJECTION_RADIUS: f64 = Constants::WGS84_SEMI_MAJOR_AXIS;
+
+pub trait Projection
+{
+  fn project(&self, coordinate: &GeoCoordinate) -> Result<(f64, f64), PositioningError>;
+  fn unproject(&self, x: f64, y: f64) -> Result<GeoCoordinate, PositioningError>;
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct WebMercator;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct PlateCarree;
+
+impl Projection for WebMercator
+{
+  fn project(&self, coordinate: &GeoCoordinate) -> Result<(f64, f64), PositioningError>
+  {
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+    let lat = coordinate.latitude
+      .clamp(-Constants::MERCATOR_MAX_LATITUDE, Constants::MERCATOR_MAX_LATITUDE)
+      .to_radians();
+    Ok((
+      PROJECTION_RADIUS * coordinate.longitude.to_radians(),
+      PROJECTION_RADIUS * (FRAC_PI_4 + lat / 2.0).tan().ln()
+    ))
+  }
+
+  fn unproject(&self, x: f64, y: f64) -> Result<GeoCoordinate, PositioningError>
+  {
+    let coordinate = GeoCoordinate::new(
+      (y / PROJECTION_RADIUS).sinh().atan().to_degrees(),
+      (x / PROJECTION_RADIUS).to_degrees(),
+      None
+    );
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(coordinate)) }
+    Ok(coordinate)
+  }
+}
+
+impl Projection for PlateCarree
+{
+  fn project(&self, coordinate: &GeoCoordinate) -> Result<(f64, f64), PositioningError>
+  {
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(*coordinate)) }
+    Ok((
+      PROJECTION_RADIUS * coordinate.longitude.to_radians(),
+      PROJECTION_RADIUS * coordinate.latitude.to_radians()
+    ))
+  }
+
+  fn unproject(&self, x: f64, y: f64) -> Result<GeoCoordinate, PositioningError>
+  {
+    let coordinate = GeoCoordinate::new(
+      (y / PROJECTION_RADIUS).to_degrees(),
+      (x / PROJECTION_RADIUS).to_degrees(),
+      None
+    );
+    if !coordinate.valid() { return Err(PositioningError::InvalidCoordinate(coordinate)) }
+    Ok(coordinate)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::positioning::GeoPath;
+
+  fn test_path() -> GeoPath
+  {
+    GeoPath::new(&vec![
+      GeoCoordinate::new(60.0, 30.0, None),
+      GeoCoordinate::new(-45.0, -179.5, None),
+      GeoCoordinate::new(0.0, 0.0, None)
+    ])
+  }
+
+  fn round_trip(projection: &impl Projection)
+  {
+    let path = test_path();
+    let projected = path.project_all(projection).unwrap();
+    assert_eq!(projected.len(), path.size());
+    for (coordinate, (x, y)) in path.iter().zip(projected) {
+      assert_eq!(projection.unproject(x, y).unwrap(), *coordinate);
+    }
+  }
+
+  #[test]
+  fn test_web_mercator()
+  {
+    round_trip(&WebMercator);
+    let (x, y) = WebMercator.project(&GeoCoordinate::new(0.0, 180.0, None)).unwrap();
+    assert!((x - 20037508.342789).abs() < 1e-3);
+    assert!(y.abs() < 1e-6);
+    assert!(WebMercator.project(&GeoCoordinate::default()).is_err());
+  }
+
+  #[test]
+  fn test_plate_carree()
+  {
+    round_trip(&PlateCarree);
+    assert!(PlateCarree.unproject(0.0, 2.0 * 20037508.342789).is_err());
+  }
+}
\ No newline at end of file
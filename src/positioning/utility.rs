@@ -1,29 +1,123 @@
-#[derive(Debug, PartialOrd, PartialEq)]
+use crate::positioning::constants as Constants;
+
+#[derive(Debug, Copy, Clone, PartialOrd, PartialEq)]
 pub enum CardinalDirection
 {
   North,
+  NorthNorthEast,
   NorthEast,
+  EastNorthEast,
   East,
+  EastSouthEast,
   SouthEast,
+  SouthSouthEast,
   South,
+  SouthSouthWest,
   SouthWest,
+  WestSouthWest,
   West,
-  NorthWest
+  WestNorthWest,
+  NorthWest,
+  NorthNorthWest
 }
 
 impl CardinalDirection
 {
+  const COMPASS: [CardinalDirection; 16] = [
+    CardinalDirection::North,
+    CardinalDirection::NorthNorthEast,
+    CardinalDirection::NorthEast,
+    CardinalDirection::EastNorthEast,
+    CardinalDirection::East,
+    CardinalDirection::EastSouthEast,
+    CardinalDirection::SouthEast,
+    CardinalDirection::SouthSouthEast,
+    CardinalDirection::South,
+    CardinalDirection::SouthSouthWest,
+    CardinalDirection::SouthWest,
+    CardinalDirection::WestSouthWest,
+    CardinalDirection::West,
+    CardinalDirection::WestNorthWest,
+    CardinalDirection::NorthWest,
+    CardinalDirection::NorthNorthWest
+  ];
+
+  pub fn from_degrees(azimuth: f32) -> CardinalDirection
+  {
+    let sector = (azimuth.rem_euclid(360.0) / 22.5).round() as usize % Self::COMPASS.len();
+    Self::COMPASS[sector]
+  }
+
+  pub fn name(&self) -> &'static str
+  {
+    match self {
+      CardinalDirection::North => "north",
+      CardinalDirection::NorthNorthEast => "north-northeast",
+      CardinalDirection::NorthEast => "northeast",
+      CardinalDirection::EastNorthEast => "east-northeast",
+      CardinalDirection::East => "east",
+      CardinalDirection::EastSouthEast => "east-southeast",
+      CardinalDirection::SouthEast => "southeast",
+      CardinalDirection::SouthSouthEast => "south-southeast",
+      CardinalDirection::South => "south",
+      CardinalDirection::SouthSouthWest => "south-southwest",
+      CardinalDirection::SouthWest => "southwest",
+      CardinalDirection::WestSouthWest => "west-southwest",
+      CardinalDirection::West => "west",
+      CardinalDirection::WestNorthWest => "west-northwest",
+      CardinalDirection::NorthWest => "northwest",
+      CardinalDirection::NorthNorthWest => "north-northwest"
+    }
+  }
+
   pub fn to_degrees(&self) -> f32
   {
     match self {
       CardinalDirection::North => 0.0,
+      CardinalDirection::NorthNorthEast => 22.5,
       CardinalDirection::NorthEast => 45.0,
+      CardinalDirection::EastNorthEast => 67.5,
       CardinalDirection::East => 90.0,
+      CardinalDirection::EastSouthEast => 112.5,
       CardinalDirection::SouthEast => 135.0,
+      CardinalDirection::SouthSouthEast => 157.5,
       CardinalDirection::South => 180.0,
+      CardinalDirection::SouthSouthWest => 202.5,
       CardinalDirection::SouthWest => 225.0,
+      CardinalDirection::WestSouthWest => 247.5,
       CardinalDirection::West => 270.0,
-      CardinalDirection::NorthWest => 315.0
+      CardinalDirection::WestNorthWest => 292.5,
+      CardinalDirection::NorthWest => 315.0,
+      CardinalDirection::NorthNorthWest => 337.5
+    }
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum DistanceUnit
+{
+  Meters,
+  Kilometers,
+  NauticalMiles
+}
+
+impl DistanceUnit
+{
+  pub fn from_meters(&self, meters: f32) -> f32
+  {
+    match self {
+      DistanceUnit::Meters => meters,
+      DistanceUnit::Kilometers => meters / 1000.0,
+      DistanceUnit::NauticalMiles => meters / Constants::NAUTICAL_MILE
+    }
+  }
+
+  pub fn symbol(&self) -> &'static str
+  {
+    match self {
+      DistanceUnit::Meters => "m",
+      DistanceUnit::Kilometers => "km",
+      DistanceUnit::NauticalMiles => "NM"
     }
   }
 }
@@ -64,4 +158,48 @@ impl CoordinateField for f64
       }
     }
   }
+}
+
+/// Brings a longitude (or longitude difference) into [-180, 180] modulo 360. Values already in range,
+/// including both 180 and -180, are returned unchanged.
+pub(crate) fn normalize_longitude(longitude: f64) -> f64
+{
+  if longitude.valid(CoordinateFieldType::Longitude) { return longitude }
+  (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_cardinal_from_degrees()
+  {
+    assert_eq!(CardinalDirection::from_degrees(0.0), CardinalDirection::North);
+    assert_eq!(CardinalDirection::from_degrees(22.0), CardinalDirection::NorthNorthEast);
+    assert_eq!(CardinalDirection::from_degrees(200.0), CardinalDirection::SouthSouthWest);
+    assert_eq!(CardinalDirection::from_degrees(350.0), CardinalDirection::North);
+    assert_eq!(CardinalDirection::from_degrees(-90.0), CardinalDirection::West);
+    assert_eq!(CardinalDirection::from_degrees(450.0), CardinalDirection::East);
+  }
+
+  #[test]
+  fn test_cardinal_round_trip()
+  {
+    for direction in CardinalDirection::COMPASS {
+      assert_eq!(CardinalDirection::from_degrees(direction.to_degrees()), direction);
+    }
+  }
+
+  #[test]
+  fn test_normalize_longitude()
+  {
+    assert_eq!(normalize_longitude(30.0), 30.0);
+    assert_eq!(normalize_longitude(180.0), 180.0);
+    assert_eq!(normalize_longitude(-180.0), -180.0);
+    assert_eq!(normalize_longitude(190.0), -170.0);
+    assert_eq!(normalize_longitude(-190.0), 170.0);
+    assert_eq!(normalize_longitude(540.0), -180.0);
+    assert_eq!(normalize_longitude(-725.0), -5.0);
+  }
 }
\ No newline at end of file